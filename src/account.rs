@@ -0,0 +1,95 @@
+//  Copyright 2021 PolyCrypt GmbH
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+//! On-chain account participants.
+//!
+//! Use [ParticipantSignature] as [Config::Signature], [ParticipantKey] as
+//! [Config::PK] and [OnChainAccounts] as [Config::AccountParticipants] to
+//! allow on-chain accounts like multisig, proxy or DAO accounts as
+//! participants of a channel.
+//! An account participant cannot sign off-chain. It approves a state or
+//! withdrawal by being the origin of the call or with [Pallet::approve].
+//! Its entry in the signatures of a call is not verified and should be set
+//! to [ParticipantSignature::Approved].
+
+use crate::*;
+
+use codec::{Decode, Encode};
+use sp_runtime::{
+	traits::{IdentifyAccount, Lazy, Verify},
+	RuntimeDebug,
+};
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+/// Participant that is either an off-chain key or an on-chain account.
+pub enum ParticipantKey<PK, AccountId> {
+	/// Off-chain participant that signs with its key.
+	Key(PK),
+	/// On-chain participant that approves with its account.
+	Account(AccountId),
+}
+
+impl<PK, AccountId> IdentifyAccount for ParticipantKey<PK, AccountId> {
+	type AccountId = Self;
+
+	fn into_account(self) -> Self {
+		self
+	}
+}
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+/// Signature of a [ParticipantKey].
+pub enum ParticipantSignature<Sig, AccountId> {
+	/// Off-chain signature of a [ParticipantKey::Key].
+	Sig(Sig),
+	/// Placeholder for a [ParticipantKey::Account] whose approval is
+	/// checked on-chain.
+	Approved(AccountId),
+}
+
+impl<Sig, AccountId> Verify for ParticipantSignature<Sig, AccountId>
+where
+	Sig: Verify,
+	Sig::Signer: Clone,
+{
+	type Signer = ParticipantKey<Sig::Signer, AccountId>;
+
+	/// Returns whether the key of `signer` signed `msg`.
+	///
+	/// Always `false` for accounts since they cannot sign off-chain.
+	fn verify<L: Lazy<[u8]>>(
+		&self,
+		mut msg: L,
+		signer: &ParticipantKey<Sig::Signer, AccountId>,
+	) -> bool {
+		match (self, signer) {
+			(Self::Sig(sig), ParticipantKey::Key(pk)) => verify_sig(msg.get(), sig, pk),
+			_ => false,
+		}
+	}
+}
+
+/// Resolves the [ParticipantKey::Account]s of a channel.
+pub struct OnChainAccounts;
+
+impl<PK, AccountId: Clone> AccountParticipants<ParticipantKey<PK, AccountId>, AccountId>
+	for OnChainAccounts
+{
+	fn account(pk: &ParticipantKey<PK, AccountId>) -> Option<AccountId> {
+		match pk {
+			ParticipantKey::Account(account) => Some(account.clone()),
+			ParticipantKey::Key(_) => None,
+		}
+	}
+}
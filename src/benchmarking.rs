@@ -3,10 +3,12 @@
 
 use super::{types::*, *};
 
-use codec::Encode;
-use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_benchmarking::{account, benchmarks, whitelisted_caller, BenchmarkError};
 use frame_system::RawOrigin;
-use sp_io::crypto::{sr25519_generate, sr25519_sign};
+use sp_core::{ecdsa, ed25519, sr25519};
+use sp_io::crypto::{
+	ecdsa_generate, ecdsa_sign, ed25519_generate, ed25519_sign, sr25519_generate, sr25519_sign,
+};
 use sp_std::vec::Vec;
 
 // Benchmarks all extrinsics and calculates weight estimations.
@@ -24,8 +26,8 @@ benchmarks! {
 			VersionOf<T>: From<u32>,
 			NonceOf<T>: From<[u8; 32]>,
 			SecondsOf<T>: From<u64>,
-			PkOf<T>: From<sp_core::sr25519::Public>,
-			SigOf<T>: From<sp_core::sr25519::Signature>,
+			PkOf<T>: From<sr25519::Public>,
+			SigOf<T>: From<sr25519::Signature>,
 	}
 
 	deposit {
@@ -33,19 +35,114 @@ benchmarks! {
 		let fid: FundingIdOf<T> = [255u8; 32].into();
 	}: _(RawOrigin::Signed(alice), fid, 500u32.into())
 
+	deposit_for {
+		let p in 2 .. 255;
+
+		let alice = setup_account::<T>();
+		let mut params = gen_params::<T>(gen_pks(p).into_iter().map(Into::into).collect());
+		params.challenge_duration = 1u64.into();
+	}: _(RawOrigin::Signed(alice), params, p - 1, 500u32.into())
+
 	dispute {
 		let p in 2 .. 255;
+		let e in 0 .. T::MaxExpiries::get().saturating_sub(1);
 		let num_parts = p;
 
-		let (alice, pks, params, state, sigs) = gen_conclude_args::<T>(num_parts, false);
-	}: _(RawOrigin::Signed(alice), params, state, sigs)
+		let (alice, pks, params, state, sigs) =
+			gen_conclude_args::<T, sr25519::Public>(num_parts, false)?;
+		fill_expiries::<T>(e);
+	}: dispute(RawOrigin::Signed(alice), params, state, sigs)
+
+	dispute_ed25519 {
+		let p in 2 .. 255;
+		let e in 0 .. T::MaxExpiries::get().saturating_sub(1);
+		let num_parts = p;
+
+		let (alice, pks, params, state, sigs) =
+			gen_conclude_args::<T, ed25519::Public>(num_parts, false)?;
+		fill_expiries::<T>(e);
+	}: dispute(RawOrigin::Signed(alice), params, state, sigs)
+
+	dispute_ecdsa {
+		let p in 2 .. 255;
+		let e in 0 .. T::MaxExpiries::get().saturating_sub(1);
+		let num_parts = p;
+
+		let (alice, pks, params, state, sigs) =
+			gen_conclude_args::<T, ecdsa::Public>(num_parts, false)?;
+		fill_expiries::<T>(e);
+	}: dispute(RawOrigin::Signed(alice), params, state, sigs)
+
+	// Refutation of a bonded dispute by a hired watcher, which slashes the
+	// bond, pays the watcher and posts a new bond.
+	refute {
+		let p in 2 .. 255;
+
+		let (alice, pks, params, state, sigs, _) = setup_hire::<T>(p)?;
+		let origin = RawOrigin::Signed(alice).into();
+		Pallet::<T>::dispute(origin, params.clone(), state.clone(), sigs)?;
+		let mut next = state;
+		next.version = 1u32.into();
+		let sigs = sign_state::<T>(&next, &pks);
+		let watcher: AccountIdOf<T> = account("watcher", 0, 0);
+	}: dispute(RawOrigin::Signed(watcher), params, next, sigs)
+
+	// Replaces an older checkpoint.
+	checkpoint {
+		let p in 2 .. 255;
+		let num_parts = p;
+
+		let (alice, pks, params, state, sigs) =
+			gen_conclude_args::<T, sr25519::Public>(num_parts, false)?;
+		let origin = RawOrigin::Signed(alice.clone()).into();
+		Pallet::<T>::checkpoint(origin, params.clone(), state.clone(), sigs)?;
+		let mut next = state;
+		next.version = 1u32.into();
+		let sigs = sign_state::<T>(&next, &pks);
+	}: _(RawOrigin::Signed(alice), params, next, sigs)
+
+	// Cancels a bonded dispute in a full index of expiries.
+	resume {
+		let p in 2 .. 255;
+		let e in 0 .. T::MaxExpiries::get().saturating_sub(1);
+		let num_parts = p;
+
+		let (alice, pks, params, state, sigs) =
+			gen_conclude_args::<T, sr25519::Public>(num_parts, false)?;
+		fill_expiries::<T>(e);
+		let origin = RawOrigin::Signed(alice.clone()).into();
+		Pallet::<T>::dispute(origin, params.clone(), state.clone(), sigs)?;
+		let mut next = state;
+		next.version = 1u32.into();
+		let sigs = sign_state::<T>(&next, &pks);
+	}: _(RawOrigin::Signed(alice), params, next, sigs)
+
+	// Verification of the sr25519 state signatures of `p` participants
+	// without the rest of a dispute. Uses the `Sr25519BatchVerifier` if the
+	// `batch-verify` feature is enabled and the `SequentialVerifier`
+	// otherwise, compare both to decide on the [Config::Verifier].
+	// The dispute benchmarks verify with the [Config::Verifier] itself.
+	#[extra]
+	verify_sr25519 {
+		let p in 2 .. 255;
+
+		let pks = gen_pks(p);
+		let msg = [255u8; 32];
+		let sigs: Vec<_> = pks.iter().map(|pk| sign(&msg, pk)).collect();
+	}: {
+		#[cfg(feature = "batch-verify")]
+		assert!(Sr25519BatchVerifier::verify_all(&msg, &sigs, &pks));
+		#[cfg(not(feature = "batch-verify"))]
+		assert!(SequentialVerifier::verify_all(&msg, &sigs, &pks));
+	}
 
 	conclude {
 		let p in 2 .. 255;
 		let num_parts = p;
 
 		// Create params and state.
-		let (alice, pks, params, state, sigs) = gen_conclude_args::<T>(num_parts, true);
+		let (alice, pks, params, state, sigs) =
+			gen_conclude_args::<T, sr25519::Public>(num_parts, true)?;
 		// Deposit
 		let fid = Pallet::<T>::calc_funding_id(state.channel_id, &pks[0].into());
 		let origin = RawOrigin::Signed(alice.clone()).into();
@@ -60,7 +157,8 @@ benchmarks! {
 		let num_parts = p;
 
 		// Create params and state.
-		let (alice, pks, params, state, sigs) = gen_conclude_args::<T>(num_parts, true);
+		let (alice, pks, params, state, sigs) =
+			gen_conclude_args::<T, sr25519::Public>(num_parts, true)?;
 		// Deposit
 		let fid = Pallet::<T>::calc_funding_id(state.channel_id, &pks[0].into());
 		let origin = RawOrigin::Signed(alice.clone()).into();
@@ -71,7 +169,8 @@ benchmarks! {
 		let num_parts = 1;
 
 		// Create params and state.
-		let (alice, pks, params, state, sigs) = gen_conclude_args::<T>(num_parts, true);
+		let (alice, pks, params, state, sigs) =
+			gen_conclude_args::<T, sr25519::Public>(num_parts, true)?;
 		// Deposit
 		let fid = Pallet::<T>::calc_funding_id(state.channel_id, &pks[0].into());
 		let origin = RawOrigin::Signed(alice.clone()).into();
@@ -84,44 +183,214 @@ benchmarks! {
 		// Withdraw
 		let (withdrawal, sig) = gen_withdraw_args::<T>(alice.clone(), pks[0], &params);
 	}: _(RawOrigin::Signed(alice), withdrawal, sig)
+
+	withdraw_all {
+		let w in 2 .. 255;
+		let num_parts = w;
+
+		// Create params and state.
+		let (alice, pks, params, state, sigs) =
+			gen_conclude_args::<T, sr25519::Public>(num_parts, true)?;
+		// All participants deposited.
+		for pk in pks.iter() {
+			let fid = Pallet::<T>::calc_funding_id(state.channel_id, &(*pk).into());
+			let origin = RawOrigin::Signed(alice.clone()).into();
+			Pallet::<T>::deposit(origin, fid, 500000u64.into())?;
+		}
+
+		// Conclude
+		let origin = RawOrigin::Signed(alice.clone()).into();
+		Pallet::<T>::conclude_final(origin, params.clone(), state, sigs)?;
+
+		// Withdraw
+		let (withdrawals, sigs): (Vec<_>, Vec<_>) = pks
+			.iter()
+			.map(|pk| gen_withdraw_args::<T>(alice.clone(), *pk, &params))
+			.unzip();
+		let channel_id = params.channel_id::<T>().unwrap();
+	}: _(RawOrigin::Signed(alice), channel_id, withdrawals, sigs)
+
+	acknowledge {
+		let p in 2 .. 255;
+		let num_parts = p;
+
+		let (alice, pks, params, state, sigs) =
+			gen_conclude_args::<T, sr25519::Public>(num_parts, false)?;
+		let origin = RawOrigin::Signed(alice.clone()).into();
+		Pallet::<T>::dispute(origin, params.clone(), state.clone(), sigs)?;
+		// All other participants already acknowledged.
+		let ack = Acknowledgement {
+			channel_id: state.channel_id,
+			version: state.version.clone(),
+		};
+		let data = Pallet::<T>::acknowledgement_payload(&ack).unwrap();
+		for (i, pk) in pks.iter().enumerate().skip(1) {
+			let origin = RawOrigin::Signed(alice.clone()).into();
+			let sig = sign(&data, pk);
+			Pallet::<T>::acknowledge(
+				origin,
+				params.clone(),
+				state.version.clone(),
+				sig.into(),
+				i as ParticipantIndex,
+			)?;
+		}
+		let sig = sign(&data, &pks[0]);
+	}: _(RawOrigin::Signed(alice), params, state.version, sig.into(), 0)
+
+	report_equivocation {
+		let p in 2 .. 255;
+		let num_parts = p;
+
+		let (alice, pks, params, state_a, _) =
+			gen_conclude_args::<T, sr25519::Public>(num_parts, false)?;
+		let mut state_b = state_a.clone();
+		state_b.balances[0] = 5u64.into();
+		let data_a = Pallet::<T>::state_payload(&state_a).unwrap();
+		let data_b = Pallet::<T>::state_payload(&state_b).unwrap();
+		// All other participants already equivocated.
+		for (i, pk) in pks.iter().enumerate().skip(1) {
+			let origin = RawOrigin::Signed(alice.clone()).into();
+			Pallet::<T>::report_equivocation(
+				origin,
+				params.clone(),
+				state_a.clone(),
+				sign(&data_a, pk).into(),
+				state_b.clone(),
+				sign(&data_b, pk).into(),
+				i as ParticipantIndex,
+			)?;
+		}
+		let sig_a = sign(&data_a, &pks[0]);
+		let sig_b = sign(&data_b, &pks[0]);
+	}: _(RawOrigin::Signed(alice), params, state_a, sig_a.into(), state_b, sig_b.into(), 0)
+
+	register_watcher {
+		let watcher = setup_watcher_account::<T>();
+		let key = gen_pk(None);
+	}: _(RawOrigin::Signed(watcher), key.into())
+
+	unregister_watcher {
+		let (watcher, _) = setup_watcher::<T>()?;
+	}: _(RawOrigin::Signed(watcher))
+
+	hire_watcher {
+		let p in 2 .. 255;
+		let num_parts = p;
+
+		let (alice, pks, params, _, _) =
+			gen_conclude_args::<T, sr25519::Public>(num_parts, false)?;
+		let (watcher, key) = setup_watcher::<T>()?;
+		// Only the last participant deposited.
+		let last: PkOf<T> = pks[pks.len() - 1].into();
+		let fid = Pallet::<T>::calc_funding_id(params.channel_id::<T>().unwrap(), &last);
+		let origin = RawOrigin::Signed(alice.clone()).into();
+		Pallet::<T>::deposit(origin, fid, 500000u64.into())?;
+		set_now::<T>(1);
+		let (offer, part_sig, watcher_sig) =
+			gen_hire_args::<T>(alice.clone(), watcher, &key, &pks[0], &params);
+	}: _(RawOrigin::Signed(alice), params, offer, 0, part_sig, watcher_sig)
+
+	release_hire {
+		let (alice, pks, params, state, sigs, _) = setup_hire::<T>(2)?;
+		// The channel was never disputed and the hire ended.
+		set_now::<T>(HIRE_END + 1);
+	}: _(RawOrigin::Signed(alice), params.channel_id::<T>().unwrap())
+
+	slash_watcher {
+		let (alice, pks, params, state, sigs, key) = setup_hire::<T>(2)?;
+		// Dispute and conclude the channel.
+		let origin = RawOrigin::Signed(alice.clone()).into();
+		Pallet::<T>::dispute(origin, params.clone(), state.clone(), sigs)?;
+		let origin = RawOrigin::Signed(alice.clone()).into();
+		Pallet::<T>::conclude(origin, params)?;
+		// The watcher signed a newer state.
+		let receipt = WatchReceipt {
+			channel_id: state.channel_id,
+			version: 1u32.into(),
+		};
+		let sig = sign(&Pallet::<T>::receipt_payload(&receipt).unwrap(), &key);
+	}: _(RawOrigin::Signed(alice), receipt, sig.into())
+
+	approve {
+		let alice = setup_account::<T>();
+		let payload_hash: T::HashValue = [255u8; 32].into();
+	}: _(RawOrigin::Signed(alice), payload_hash)
 }
 
 /// Generates arguments for `Pallet::conclude` and `Pallet::dispute`.
-fn gen_conclude_args<T: Config>(
+///
+/// The participants use keys of the scheme `K`. Skips the benchmark if
+/// the runtime does not support the scheme, see [BenchmarkHelper].
+fn gen_conclude_args<T: Config, K: BenchKey<T>>(
 	num_parts: u32,
 	is_final: bool,
-) -> (
-	AccountIdOf<T>,
-	Vec<sp_core::sr25519::Public>,
-	ParamsOf<T>,
-	StateOf<T>,
-	Vec<SigOf<T>>,
-)
+) -> Result<
+	(
+		AccountIdOf<T>,
+		Vec<K>,
+		ParamsOf<T>,
+		StateOf<T>,
+		Vec<SigOf<T>>,
+	),
+	BenchmarkError,
+>
 where
 	BalanceOf<T>: From<u64>,
 	FundingIdOf<T>: From<[u8; 32]>,
 	VersionOf<T>: From<u32>,
 	NonceOf<T>: From<[u8; 32]>,
 	SecondsOf<T>: From<u64>,
-	PkOf<T>: From<sp_core::sr25519::Public>,
-	SigOf<T>: From<sp_core::sr25519::Signature>,
 {
 	let alice = setup_account::<T>();
-	let pks = gen_pks(num_parts);
+	let pks: Vec<K> = (0..num_parts).map(|_| K::generate()).collect();
 	// Generate params and state.
-	let params = gen_params::<T>(pks.clone());
+	let parts = pks
+		.iter()
+		.map(|pk| pk.participant().ok_or(BenchmarkError::Skip))
+		.collect::<Result<Vec<_>, _>>()?;
+	let params = gen_params::<T>(parts);
 	let state = gen_state::<T>(&params, is_final);
 
 	// Sign the state with all participants.
-	let data = Encode::encode(&state);
-	let sigs: Vec<SigOf<T>> = pks.iter().map(|pk| sign(&data, pk).into()).collect();
-	(alice, pks, params, state, sigs)
+	let data = Pallet::<T>::state_payload(&state).unwrap();
+	let sigs = pks
+		.iter()
+		.map(|pk| K::signature(pk.sign(&data)).ok_or(BenchmarkError::Skip))
+		.collect::<Result<Vec<_>, _>>()?;
+	Ok((alice, pks, params, state, sigs))
+}
+
+/// Signs `state` with all `pks`.
+fn sign_state<T: Config>(state: &StateOf<T>, pks: &[sr25519::Public]) -> Vec<SigOf<T>>
+where
+	SigOf<T>: From<sr25519::Signature>,
+{
+	let data = Pallet::<T>::state_payload(state).unwrap();
+	pks.iter().map(|pk| sign(&data, pk).into()).collect()
+}
+
+/// Fills [Expiries] with `e` disputes that expire after all others.
+///
+/// New disputes are inserted in front of them.
+fn fill_expiries<T: Config>(e: u32)
+where
+	FundingIdOf<T>: From<[u8; 32]>,
+{
+	let expiries: Vec<_> = (0..e)
+		.map(|i| {
+			let mut id = [0u8; 32];
+			id[..4].copy_from_slice(&i.to_le_bytes());
+			(u64::MAX.into(), id.into())
+		})
+		.collect();
+	Expiries::<T>::put(BoundedVec::truncate_from(expiries));
 }
 
 /// Generates arguments for `Pallet::withdraw`.
 fn gen_withdraw_args<T: Config>(
 	alice_id: AccountIdOf<T>,
-	alice_pk: sp_core::sr25519::Public,
+	alice_pk: sr25519::Public,
 	params: &ParamsOf<T>,
 ) -> (WithdrawalOf<T>, SigOf<T>)
 where
@@ -130,20 +399,131 @@ where
 	VersionOf<T>: From<u32>,
 	NonceOf<T>: From<[u8; 32]>,
 	SecondsOf<T>: From<u64>,
-	PkOf<T>: From<sp_core::sr25519::Public>,
-	SigOf<T>: From<sp_core::sr25519::Signature>,
+	PkOf<T>: From<sr25519::Public>,
+	SigOf<T>: From<sr25519::Signature>,
 {
 	let withdrawal = WithdrawalOf::<T> {
-		channel_id: params.channel_id::<HasherOf<T>>(),
+		channel_id: params.channel_id::<T>().unwrap(),
 		part: alice_pk.into(),
 		receiver: alice_id,
+		expiry: None,
+		splits: Vec::new(),
+		fee: 0u32.into(),
+		relayer: None,
 	};
-	let data = Encode::encode(&withdrawal);
+	let fid = Pallet::<T>::calc_funding_id(withdrawal.channel_id, &withdrawal.part);
+	let amount = Pallet::<T>::deposits(fid).unwrap_or_default();
+	let data = Pallet::<T>::withdrawal_payload(&withdrawal, &amount).unwrap();
 	let sig = sign(&data, &alice_pk);
 
 	(withdrawal, sig.into())
 }
 
+/// End of the hires of the benchmarks in seconds.
+const HIRE_END: u32 = 5;
+
+/// Generates arguments for `Pallet::hire_watcher`.
+///
+/// The offer is signed by the watcher `key` and participant `part`.
+fn gen_hire_args<T: Config>(
+	registrant: AccountIdOf<T>,
+	watcher: AccountIdOf<T>,
+	key: &sr25519::Public,
+	part: &sr25519::Public,
+	params: &ParamsOf<T>,
+) -> (HireOfferOf<T>, SigOf<T>, SigOf<T>)
+where
+	BalanceOf<T>: From<u64>,
+	SecondsOf<T>: From<u64>,
+	SigOf<T>: From<sr25519::Signature>,
+{
+	let offer = HireOffer {
+		channel_id: params.channel_id::<T>().unwrap(),
+		registrant,
+		watcher,
+		fee: 100u64.into(),
+		until: (HIRE_END as u64).into(),
+	};
+	let payload = Pallet::<T>::hire_offer_payload(&offer).unwrap();
+	(
+		offer,
+		sign(&payload, part).into(),
+		sign(&payload, key).into(),
+	)
+}
+
+/// Registers a funded watcher with a new key.
+fn setup_watcher<T: Config>() -> Result<(AccountIdOf<T>, sr25519::Public), BenchmarkError>
+where
+	BalanceOf<T>: From<u64>,
+	PkOf<T>: From<sr25519::Public>,
+{
+	let watcher = setup_watcher_account::<T>();
+	let key = gen_pk(None);
+	let origin = RawOrigin::Signed(watcher.clone()).into();
+	Pallet::<T>::register_watcher(origin, key.into())?;
+	Ok((watcher, key))
+}
+
+/// Hires a watcher for a funded channel of `num_parts` participants.
+///
+/// Returns the arguments of the channel like [gen_conclude_args] and the
+/// key of the watcher.
+#[allow(clippy::type_complexity)]
+fn setup_hire<T: Config>(
+	num_parts: u32,
+) -> Result<
+	(
+		AccountIdOf<T>,
+		Vec<sr25519::Public>,
+		ParamsOf<T>,
+		StateOf<T>,
+		Vec<SigOf<T>>,
+		sr25519::Public,
+	),
+	BenchmarkError,
+>
+where
+	BalanceOf<T>: From<u64>,
+	FundingIdOf<T>: From<[u8; 32]>,
+	VersionOf<T>: From<u32>,
+	NonceOf<T>: From<[u8; 32]>,
+	SecondsOf<T>: From<u64>,
+	PkOf<T>: From<sr25519::Public>,
+	SigOf<T>: From<sr25519::Signature>,
+{
+	let (alice, pks, params, state, sigs) =
+		gen_conclude_args::<T, sr25519::Public>(num_parts, false)?;
+	let (watcher, key) = setup_watcher::<T>()?;
+	let fid = Pallet::<T>::calc_funding_id(state.channel_id, &pks[0].into());
+	let origin = RawOrigin::Signed(alice.clone()).into();
+	Pallet::<T>::deposit(origin, fid, 500000u64.into())?;
+
+	set_now::<T>(1);
+	let (offer, part_sig, watcher_sig) =
+		gen_hire_args::<T>(alice.clone(), watcher, &key, &pks[0], &params);
+	let origin = RawOrigin::Signed(alice.clone()).into();
+	Pallet::<T>::hire_watcher(origin, params.clone(), offer, 0, part_sig, watcher_sig)?;
+	Ok((alice, pks, params, state, sigs, key))
+}
+
+/// Sets the current time to `secs` seconds.
+///
+/// Writes the timestamp directly so that no consensus hooks are triggered.
+fn set_now<T: Config>(secs: u32) {
+	pallet_timestamp::Now::<T>::put(T::Moment::from(secs * 1000));
+}
+
+/// Returns a funded Account that is not whitelisted, e.g. for a watcher.
+fn setup_watcher_account<T: Config>() -> AccountIdOf<T>
+where
+	BalanceOf<T>: From<u64>,
+{
+	let watcher: AccountIdOf<T> = account("watcher", 0, 0);
+	CurrencyOf::<T>::make_free_balance_be(&watcher, 100000000000000000u64.into());
+	watcher
+}
+
 /// Returns a whitelisted and funded Account that can be used to send Extrinsics.
 fn setup_account<T: Config>() -> AccountIdOf<T>
 where
@@ -156,19 +536,18 @@ where
 }
 
 /// Generates Params. Uses the passed public keys as participants.
-fn gen_params<T: Config>(pks: Vec<sp_core::sr25519::Public>) -> ParamsOf<T>
+fn gen_params<T: Config>(parts: Vec<PkOf<T>>) -> ParamsOf<T>
 where
 	NonceOf<T>: From<[u8; 32]>,
-	PkOf<T>: From<sp_core::sr25519::Public>,
 	SecondsOf<T>: From<u64>,
 {
-	let parts: Vec<PkOf<T>> = pks.into_iter().map(|pk| pk.into()).collect();
-
 	Params {
 		nonce: [0u8; 32].into(),
 		participants: parts,
 		challenge_duration: 0u64.into(),
 		app: T::NoApp::get(),
+		app_params: Default::default(),
+		payouts: Vec::new(),
 	}
 }
 
@@ -182,7 +561,7 @@ where
 	let bals: Vec<BalanceOf<T>> = params.participants.iter().map(|_| 10u64.into()).collect();
 
 	State {
-		channel_id: params.channel_id::<HasherOf<T>>(),
+		channel_id: params.channel_id::<T>().unwrap(),
 		version: 0u32.into(),
 		balances: bals,
 		finalized: is_final,
@@ -191,18 +570,163 @@ where
 }
 
 /// Generates public keys that can be used to sign.
-pub fn gen_pks(num_parts: u32) -> Vec<sp_core::sr25519::Public> {
+pub fn gen_pks(num_parts: u32) -> Vec<sr25519::Public> {
 	(0..num_parts).map(|_| gen_pk(None)).collect()
 }
 
 /// Creates a public key that can later on be used to sign.
 /// The secret key remains in the `keystore` if the node.
-pub fn gen_pk(seed: Option<Vec<u8>>) -> sp_core::sr25519::Public {
+pub fn gen_pk(seed: Option<Vec<u8>>) -> sr25519::Public {
 	sr25519_generate(0.into(), seed)
 }
 
 /// Signs the payload on behalf of a public key that was created by
 /// `gen_pk`.
-pub fn sign(payload: &[u8], pubkey: &sp_core::sr25519::Public) -> sp_core::sr25519::Signature {
+pub fn sign(payload: &[u8], pubkey: &sr25519::Public) -> sr25519::Signature {
 	sr25519_sign(0.into(), pubkey, payload).unwrap()
 }
+
+/// Converts the keys of the benchmarked signature schemes into
+/// participant keys.
+///
+/// Is configured with [Config::BenchmarkHelper]. The sr25519 benchmarks
+/// always run, the ed25519 and ecdsa ones are skipped for schemes that
+/// the runtime does not support.
+pub trait BenchmarkHelper<T: Config> {
+	/// Returns the participant of an ed25519 key, if supported.
+	fn ed25519(pk: ed25519::Public) -> Option<PkOf<T>>;
+	/// Returns the signature of an ed25519 signature, if supported.
+	fn ed25519_sig(sig: ed25519::Signature) -> Option<SigOf<T>>;
+	/// Returns the participant of an ecdsa key, if supported.
+	fn ecdsa(pk: ecdsa::Public) -> Option<PkOf<T>>;
+	/// Returns the signature of an ecdsa signature, if supported.
+	fn ecdsa_sig(sig: ecdsa::Signature) -> Option<SigOf<T>>;
+}
+
+/// Supports only sr25519 participants.
+impl<T: Config> BenchmarkHelper<T> for () {
+	fn ed25519(_: ed25519::Public) -> Option<PkOf<T>> {
+		None
+	}
+
+	fn ed25519_sig(_: ed25519::Signature) -> Option<SigOf<T>> {
+		None
+	}
+
+	fn ecdsa(_: ecdsa::Public) -> Option<PkOf<T>> {
+		None
+	}
+
+	fn ecdsa_sig(_: ecdsa::Signature) -> Option<SigOf<T>> {
+		None
+	}
+}
+
+/// Supports all schemes, e.g. for [sp_runtime::MultiSigner] participants.
+pub struct AllSchemes;
+
+impl<T: Config> BenchmarkHelper<T> for AllSchemes
+where
+	PkOf<T>: From<ed25519::Public> + From<ecdsa::Public>,
+	SigOf<T>: From<ed25519::Signature> + From<ecdsa::Signature>,
+{
+	fn ed25519(pk: ed25519::Public) -> Option<PkOf<T>> {
+		Some(pk.into())
+	}
+
+	fn ed25519_sig(sig: ed25519::Signature) -> Option<SigOf<T>> {
+		Some(sig.into())
+	}
+
+	fn ecdsa(pk: ecdsa::Public) -> Option<PkOf<T>> {
+		Some(pk.into())
+	}
+
+	fn ecdsa_sig(sig: ecdsa::Signature) -> Option<SigOf<T>> {
+		Some(sig.into())
+	}
+}
+
+/// Public key of a signature scheme that can be benchmarked.
+pub trait BenchKey<T: Config>: Clone {
+	type Signature;
+
+	/// Creates a public key that can later on be used to sign.
+	/// The secret key remains in the `keystore` of the node.
+	fn generate() -> Self;
+
+	/// Signs the payload on behalf of the public key.
+	fn sign(&self, payload: &[u8]) -> Self::Signature;
+
+	/// Returns the key as participant, `None` if the runtime does not
+	/// support the scheme.
+	fn participant(&self) -> Option<PkOf<T>>;
+
+	/// Returns the signature of a participant, `None` if the runtime does
+	/// not support the scheme.
+	fn signature(sig: Self::Signature) -> Option<SigOf<T>>;
+}
+
+impl<T: Config> BenchKey<T> for sr25519::Public
+where
+	PkOf<T>: From<sr25519::Public>,
+	SigOf<T>: From<sr25519::Signature>,
+{
+	type Signature = sr25519::Signature;
+
+	fn generate() -> Self {
+		gen_pk(None)
+	}
+
+	fn sign(&self, payload: &[u8]) -> Self::Signature {
+		sign(payload, self)
+	}
+
+	fn participant(&self) -> Option<PkOf<T>> {
+		Some((*self).into())
+	}
+
+	fn signature(sig: Self::Signature) -> Option<SigOf<T>> {
+		Some(sig.into())
+	}
+}
+
+impl<T: Config> BenchKey<T> for ed25519::Public {
+	type Signature = ed25519::Signature;
+
+	fn generate() -> Self {
+		ed25519_generate(0.into(), None)
+	}
+
+	fn sign(&self, payload: &[u8]) -> Self::Signature {
+		ed25519_sign(0.into(), self, payload).unwrap()
+	}
+
+	fn participant(&self) -> Option<PkOf<T>> {
+		T::BenchmarkHelper::ed25519(*self)
+	}
+
+	fn signature(sig: Self::Signature) -> Option<SigOf<T>> {
+		T::BenchmarkHelper::ed25519_sig(sig)
+	}
+}
+
+impl<T: Config> BenchKey<T> for ecdsa::Public {
+	type Signature = ecdsa::Signature;
+
+	fn generate() -> Self {
+		ecdsa_generate(0.into(), None)
+	}
+
+	fn sign(&self, payload: &[u8]) -> Self::Signature {
+		ecdsa_sign(0.into(), self, payload).unwrap()
+	}
+
+	fn participant(&self) -> Option<PkOf<T>> {
+		T::BenchmarkHelper::ecdsa(self.clone())
+	}
+
+	fn signature(sig: Self::Signature) -> Option<SigOf<T>> {
+		T::BenchmarkHelper::ecdsa_sig(sig)
+	}
+}
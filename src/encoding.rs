@@ -0,0 +1,457 @@
+//  Copyright 2021 PolyCrypt GmbH
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+//! Encodings of channel ids, funding ids and signed messages.
+//!
+//! [ScaleEncoding] is the native encoding of the pallet.
+//! [AbiEncoding] follows the `abi.encode` layout of the go-perun Ethereum
+//! contracts, so that go-perun clients can reuse their Ethereum backend.
+
+use crate::*;
+
+use crate::eth::EthAddress;
+use sp_io::hashing::keccak_256;
+use sp_std::{marker::PhantomData, prelude::*};
+
+/// Encodes the messages that are hashed or signed by the pallet.
+///
+/// Is configured with [Config::Encoding].
+/// Fails with [ValueTooLarge] if a value does not fit into its encoded type.
+pub trait ChannelEncoding<T: Config> {
+	/// Encodes the params of a channel. Their hash is the channel id.
+	fn encode_params(params: &ParamsOf<T>) -> Result<Vec<u8>, ValueTooLarge>;
+
+	/// Encodes a state that is signed by the participants.
+	fn encode_state(state: &StateOf<T>) -> Result<Vec<u8>, ValueTooLarge>;
+
+	/// Encodes a withdrawal that is signed by a participant.
+	///
+	/// `amount` is the outcome of the participant that is withdrawn.
+	fn encode_withdrawal(
+		withdrawal: &WithdrawalOf<T>,
+		amount: &BalanceOf<T>,
+	) -> Result<Vec<u8>, ValueTooLarge>;
+
+	/// Encodes a participant of a channel. Its hash is the funding id.
+	fn encode_funding(channel: &ChannelIdOf<T>, part: &PkOf<T>) -> Vec<u8>;
+
+	/// Encodes an acknowledgement that is signed by a participant.
+	fn encode_acknowledgement(ack: &AcknowledgementOf<T>) -> Result<Vec<u8>, ValueTooLarge>;
+
+	/// Encodes a receipt that is signed by a watcher.
+	fn encode_receipt(receipt: &WatchReceiptOf<T>) -> Result<Vec<u8>, ValueTooLarge>;
+
+	/// Encodes a hire offer that is signed by a watcher and a participant.
+	fn encode_hire_offer(offer: &HireOfferOf<T>) -> Result<Vec<u8>, ValueTooLarge>;
+}
+
+/// A value does not fit into the type that it is encoded as.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct ValueTooLarge;
+
+/// SCALE encoding of all messages.
+///
+/// The withdrawn amount is not part of an encoded withdrawal.
+pub struct ScaleEncoding;
+
+impl<T: Config> ChannelEncoding<T> for ScaleEncoding {
+	fn encode_params(params: &ParamsOf<T>) -> Result<Vec<u8>, ValueTooLarge> {
+		Ok(params.encode())
+	}
+
+	fn encode_state(state: &StateOf<T>) -> Result<Vec<u8>, ValueTooLarge> {
+		Ok(state.encode())
+	}
+
+	fn encode_withdrawal(
+		withdrawal: &WithdrawalOf<T>,
+		_: &BalanceOf<T>,
+	) -> Result<Vec<u8>, ValueTooLarge> {
+		Ok(withdrawal.encode())
+	}
+
+	fn encode_funding(channel: &ChannelIdOf<T>, part: &PkOf<T>) -> Vec<u8> {
+		Funding { channel, part }.encode()
+	}
+
+	fn encode_acknowledgement(ack: &AcknowledgementOf<T>) -> Result<Vec<u8>, ValueTooLarge> {
+		Ok(ack.encode())
+	}
+
+	fn encode_receipt(receipt: &WatchReceiptOf<T>) -> Result<Vec<u8>, ValueTooLarge> {
+		Ok(receipt.encode())
+	}
+
+	fn encode_hire_offer(offer: &HireOfferOf<T>) -> Result<Vec<u8>, ValueTooLarge> {
+		Ok(offer.encode())
+	}
+}
+
+/// Ethereum ABI encoding of all messages, as used by go-perun.
+///
+/// Byte-like values like nonces, keys and accounts are encoded as
+/// left-padded 32 byte words. Values that are longer than 32 bytes are
+/// replaced by their keccak256 hash. Versions must fit into `uint64` and
+/// all other numbers into `u128`, otherwise the encoding fails. Use [EthAddress] as [Config::PK]
+/// so that participants are encoded as Ethereum addresses.
+///
+/// The outcome of a [State] is encoded as go-perun `Allocation` of the
+/// single asset `Asset`, which is the address of the asset holder contract
+/// that go-perun clients expect. `()` uses the zero address.
+pub struct AbiEncoding<Asset = ()>(PhantomData<Asset>);
+
+impl<Asset: Get<EthAddress>> AbiEncoding<Asset> {
+	/// Encodes `params` as go-perun `Params` struct.
+	///
+	/// [Params::app_params] and [Params::payouts] are not known to go-perun.
+	/// The app params are appended as `bytes` if they or the payouts are
+	/// set. The payouts are appended as `address[]` if they are set.
+	pub fn params<Nonce, PK, AccountId, Seconds, AppId, AppParams>(
+		params: &Params<Nonce, PK, AccountId, Seconds, AppId, AppParams>,
+	) -> Result<Vec<u8>, ValueTooLarge>
+	where
+		Nonce: AsRef<[u8]>,
+		PK: AsRef<[u8]>,
+		AccountId: AsRef<[u8]>,
+		Seconds: TryInto<u128> + Clone,
+		AppId: AsRef<[u8]>,
+		AppParams: AsRef<[u8]>,
+	{
+		let mut fields = vec![
+			Token::Word(try_uint(params.challenge_duration.clone())?),
+			Token::Word(word(params.nonce.as_ref())),
+			Token::Words(
+				params
+					.participants
+					.iter()
+					.map(|p| word(p.as_ref()))
+					.collect(),
+			),
+			Token::Word(word(params.app.as_ref())),
+			// Ledger channel.
+			Token::Word(uint(1)),
+			// Virtual channel.
+			Token::Word(uint(0)),
+		];
+		let app_params = params.app_params.as_ref();
+		if !app_params.is_empty() || !params.payouts.is_empty() {
+			fields.push(Token::Bytes(app_params.to_vec()));
+		}
+		if !params.payouts.is_empty() {
+			fields.push(Token::Words(
+				params.payouts.iter().map(|a| word(a.as_ref())).collect(),
+			));
+		}
+		Ok(dynamic(&fields))
+	}
+
+	/// Encodes `state` as go-perun `State` struct.
+	///
+	/// The outcome is an `Allocation` with `Asset` as only asset, the
+	/// balances of `state` and no locked sub-allocations.
+	pub fn state<ChannelId, Version, Balance>(
+		state: &State<ChannelId, Version, Balance>,
+	) -> Result<Vec<u8>, ValueTooLarge>
+	where
+		ChannelId: AsRef<[u8]>,
+		Version: TryInto<u64> + Clone,
+		Balance: TryInto<u128> + Clone,
+	{
+		let outcome = Token::Tuple(vec![
+			// assets
+			Token::Words(vec![word(&Asset::get().0)]),
+			// balances
+			Token::Array(vec![Token::Words(
+				state
+					.balances
+					.iter()
+					.map(|b| try_uint(b.clone()))
+					.collect::<Result<_, _>>()?,
+			)]),
+			// locked
+			Token::Array(vec![]),
+		]);
+		Ok(dynamic(&[
+			Token::Word(word(state.channel_id.as_ref())),
+			Token::Word(version(state.version.clone())?),
+			outcome,
+			Token::Bytes(state.data.clone()),
+			Token::Word(uint(state.finalized.into())),
+		]))
+	}
+
+	/// Encodes `withdrawal` as go-perun `WithdrawalAuth` struct.
+	///
+	/// `amount` is the withdrawn outcome of the participant.
+	/// [Withdrawal::expiry], [Withdrawal::splits], [Withdrawal::fee] and
+	/// [Withdrawal::relayer] are not known to go-perun. They are only
+	/// appended as `uint256`, `address[]`, `uint256[]`, `uint256` and
+	/// `address` if one of them is set. A missing expiry or relayer is
+	/// encoded as zero.
+	pub fn withdrawal<ChannelId, PK, AccountId, Seconds, Balance>(
+		withdrawal: &Withdrawal<ChannelId, PK, AccountId, Seconds, Balance>,
+		amount: &Balance,
+	) -> Result<Vec<u8>, ValueTooLarge>
+	where
+		ChannelId: AsRef<[u8]>,
+		PK: AsRef<[u8]>,
+		AccountId: AsRef<[u8]>,
+		Seconds: TryInto<u128> + Clone,
+		Balance: TryInto<u128> + Clone,
+	{
+		let mut fields = vec![
+			Token::Word(word(withdrawal.channel_id.as_ref())),
+			Token::Word(word(withdrawal.part.as_ref())),
+			Token::Word(word(withdrawal.receiver.as_ref())),
+			Token::Word(try_uint(amount.clone())?),
+		];
+		let fee = try_uint(withdrawal.fee.clone())?;
+		if withdrawal.expiry.is_none()
+			&& withdrawal.splits.is_empty()
+			&& fee == uint(0)
+			&& withdrawal.relayer.is_none()
+		{
+			return Ok(tuple(&fields));
+		}
+		let expiry = match withdrawal.expiry.clone() {
+			Some(expiry) => try_uint(expiry)?,
+			None => uint(0),
+		};
+		fields.push(Token::Word(expiry));
+		fields.push(Token::Words(
+			withdrawal
+				.splits
+				.iter()
+				.map(|(account, _)| word(account.as_ref()))
+				.collect(),
+		));
+		fields.push(Token::Words(
+			withdrawal
+				.splits
+				.iter()
+				.map(|(_, amount)| try_uint(amount.clone()))
+				.collect::<Result<_, _>>()?,
+		));
+		fields.push(Token::Word(fee));
+		fields.push(Token::Word(
+			withdrawal
+				.relayer
+				.as_ref()
+				.map_or([0u8; 32], |relayer| word(relayer.as_ref())),
+		));
+		Ok(dynamic(&fields))
+	}
+
+	/// Encodes a participant of a channel as `(bytes32, address)`.
+	pub fn funding<ChannelId: AsRef<[u8]>, PK: AsRef<[u8]>>(
+		channel: &ChannelId,
+		part: &PK,
+	) -> Vec<u8> {
+		tuple(&[
+			Token::Word(word(channel.as_ref())),
+			Token::Word(word(part.as_ref())),
+		])
+	}
+
+	/// Encodes `ack` as `(bytes32 channelID, uint64 version)`.
+	pub fn acknowledgement<ChannelId, Version>(
+		ack: &Acknowledgement<ChannelId, Version>,
+	) -> Result<Vec<u8>, ValueTooLarge>
+	where
+		ChannelId: AsRef<[u8]>,
+		Version: TryInto<u64> + Clone,
+	{
+		Ok(tuple(&[
+			Token::Word(word(ack.channel_id.as_ref())),
+			Token::Word(version(ack.version.clone())?),
+		]))
+	}
+
+	/// Encodes `receipt` as `(bytes32 channelID, uint64 version)`.
+	pub fn receipt<ChannelId, Version>(
+		receipt: &WatchReceipt<ChannelId, Version>,
+	) -> Result<Vec<u8>, ValueTooLarge>
+	where
+		ChannelId: AsRef<[u8]>,
+		Version: TryInto<u64> + Clone,
+	{
+		Ok(tuple(&[
+			Token::Word(word(receipt.channel_id.as_ref())),
+			Token::Word(version(receipt.version.clone())?),
+		]))
+	}
+
+	/// Encodes `offer` as
+	/// `(bytes32 channelID, address registrant, address watcher, uint256 fee, uint256 until)`.
+	pub fn hire_offer<ChannelId, AccountId, Balance, Seconds>(
+		offer: &HireOffer<ChannelId, AccountId, Balance, Seconds>,
+	) -> Result<Vec<u8>, ValueTooLarge>
+	where
+		ChannelId: AsRef<[u8]>,
+		AccountId: AsRef<[u8]>,
+		Balance: TryInto<u128> + Clone,
+		Seconds: TryInto<u128> + Clone,
+	{
+		Ok(tuple(&[
+			Token::Word(word(offer.channel_id.as_ref())),
+			Token::Word(word(offer.registrant.as_ref())),
+			Token::Word(word(offer.watcher.as_ref())),
+			Token::Word(try_uint(offer.fee.clone())?),
+			Token::Word(try_uint(offer.until.clone())?),
+		]))
+	}
+}
+
+impl<T: Config, Asset: Get<EthAddress>> ChannelEncoding<T> for AbiEncoding<Asset>
+where
+	NonceOf<T>: AsRef<[u8]>,
+	PkOf<T>: AsRef<[u8]>,
+	AppIdOf<T>: AsRef<[u8]>,
+	ChannelIdOf<T>: AsRef<[u8]>,
+	AccountIdOf<T>: AsRef<[u8]>,
+	SecondsOf<T>: TryInto<u128>,
+	VersionOf<T>: TryInto<u64>,
+	BalanceOf<T>: TryInto<u128>,
+{
+	fn encode_params(params: &ParamsOf<T>) -> Result<Vec<u8>, ValueTooLarge> {
+		Self::params(params)
+	}
+
+	fn encode_state(state: &StateOf<T>) -> Result<Vec<u8>, ValueTooLarge> {
+		Self::state(state)
+	}
+
+	fn encode_withdrawal(
+		withdrawal: &WithdrawalOf<T>,
+		amount: &BalanceOf<T>,
+	) -> Result<Vec<u8>, ValueTooLarge> {
+		Self::withdrawal(withdrawal, amount)
+	}
+
+	fn encode_funding(channel: &ChannelIdOf<T>, part: &PkOf<T>) -> Vec<u8> {
+		Self::funding(channel, part)
+	}
+
+	fn encode_acknowledgement(ack: &AcknowledgementOf<T>) -> Result<Vec<u8>, ValueTooLarge> {
+		Self::acknowledgement(ack)
+	}
+
+	fn encode_receipt(receipt: &WatchReceiptOf<T>) -> Result<Vec<u8>, ValueTooLarge> {
+		Self::receipt(receipt)
+	}
+
+	fn encode_hire_offer(offer: &HireOfferOf<T>) -> Result<Vec<u8>, ValueTooLarge> {
+		Self::hire_offer(offer)
+	}
+}
+
+/// Element of an ABI encoded tuple.
+enum Token {
+	/// Static 32 byte word.
+	Word([u8; 32]),
+	/// Dynamic `bytes`.
+	Bytes(Vec<u8>),
+	/// Dynamic array of static words.
+	Words(Vec<[u8; 32]>),
+	/// Dynamic array of dynamic tokens.
+	Array(Vec<Token>),
+	/// Tuple that contains a dynamic token.
+	Tuple(Vec<Token>),
+}
+
+/// Encodes an unsigned integer as big-endian word.
+fn uint(value: u128) -> [u8; 32] {
+	let mut word = [0u8; 32];
+	word[16..].copy_from_slice(&value.to_be_bytes());
+	word
+}
+
+/// Encodes `value` as big-endian word if it fits into `u128`.
+fn try_uint<V: TryInto<u128>>(value: V) -> Result<[u8; 32], ValueTooLarge> {
+	value.try_into().map(uint).map_err(|_| ValueTooLarge)
+}
+
+/// Encodes a version as `uint64` word if it fits.
+fn version<V: TryInto<u64>>(version: V) -> Result<[u8; 32], ValueTooLarge> {
+	let version: u64 = version.try_into().map_err(|_| ValueTooLarge)?;
+	Ok(uint(version.into()))
+}
+
+/// Left-pads `bytes` to a word.
+///
+/// Longer values are hashed with keccak256 so that they cannot collide
+/// by truncation.
+fn word(bytes: &[u8]) -> [u8; 32] {
+	if bytes.len() > 32 {
+		return keccak_256(bytes);
+	}
+	let mut word = [0u8; 32];
+	word[32 - bytes.len()..].copy_from_slice(bytes);
+	word
+}
+
+/// Encodes `tokens` as tuple.
+fn tuple(tokens: &[Token]) -> Vec<u8> {
+	let head_len = 32 * tokens.len();
+	let mut head = Vec::with_capacity(head_len);
+	let mut tail = Vec::new();
+
+	for token in tokens {
+		match token {
+			Token::Word(word) => head.extend_from_slice(word),
+			dynamic => {
+				head.extend_from_slice(&uint((head_len + tail.len()) as u128));
+				tail.append(&mut encode_dynamic(dynamic));
+			}
+		}
+	}
+	head.append(&mut tail);
+	head
+}
+
+/// Encodes the tail of a dynamic token.
+fn encode_dynamic(token: &Token) -> Vec<u8> {
+	match token {
+		Token::Word(word) => word.to_vec(),
+		Token::Bytes(bytes) => {
+			let mut encoded = uint(bytes.len() as u128).to_vec();
+			encoded.extend_from_slice(bytes);
+			// Right-pad to a multiple of 32 bytes.
+			encoded.resize(encoded.len() + (32 - bytes.len() % 32) % 32, 0);
+			encoded
+		}
+		Token::Words(words) => {
+			let mut encoded = uint(words.len() as u128).to_vec();
+			for word in words {
+				encoded.extend_from_slice(word);
+			}
+			encoded
+		}
+		Token::Array(tokens) => {
+			let mut encoded = uint(tokens.len() as u128).to_vec();
+			encoded.append(&mut tuple(tokens));
+			encoded
+		}
+		Token::Tuple(tokens) => tuple(tokens),
+	}
+}
+
+/// Encodes `tokens` as the only argument of `abi.encode`.
+///
+/// The tuple must contain dynamic tokens. It is then preceded by its offset.
+fn dynamic(tokens: &[Token]) -> Vec<u8> {
+	let mut encoded = uint(32).to_vec();
+	encoded.append(&mut tuple(tokens));
+	encoded
+}
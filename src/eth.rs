@@ -0,0 +1,129 @@
+//  Copyright 2021 PolyCrypt GmbH
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+//! Ethereum compatible signing mode.
+//!
+//! Use [EthSignature] as [Config::Signature] and [EthAddress] as [Config::PK]
+//! to verify signatures that were created by the Ethereum wallet backend of
+//! go-perun. A message is hashed with keccak and signed with the
+//! [EIP-191](https://eips.ethereum.org/EIPS/eip-191) prefix, see
+//! [eth_message_hash]. Participants are identified by their 20 byte
+//! Ethereum address that is recovered from the signature.
+
+use crate::*;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use sp_io::{crypto::secp256k1_ecdsa_recover, hashing::keccak_256};
+use sp_runtime::{
+	traits::{IdentifyAccount, Lazy, Verify},
+	RuntimeDebug,
+};
+
+/// Prefix of an EIP-191 signed message that contains a 32 byte hash.
+pub const EIP191_PREFIX: &[u8] = b"\x19Ethereum Signed Message:\n32";
+
+/// Returns the hash that an Ethereum wallet signs for `msg`.
+///
+/// Calculated as `keccak(EIP191_PREFIX ++ keccak(msg))`.
+pub fn eth_message_hash(msg: &[u8]) -> [u8; 32] {
+	let mut prefixed = EIP191_PREFIX.to_vec();
+	prefixed.extend_from_slice(&keccak_256(msg));
+	keccak_256(&prefixed)
+}
+
+#[derive(
+	Encode,
+	Decode,
+	MaxEncodedLen,
+	Default,
+	Copy,
+	Clone,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	RuntimeDebug,
+	TypeInfo,
+)]
+/// Ethereum address of an off-chain participant.
+pub struct EthAddress(pub [u8; 20]);
+
+impl EthAddress {
+	/// Derives the address of an uncompressed secp256k1 public key.
+	///
+	/// The key is expected without the leading `0x04` tag.
+	pub fn from_uncompressed(pk: &[u8; 64]) -> Self {
+		let hash = keccak_256(pk);
+		let mut address = [0u8; 20];
+		address.copy_from_slice(&hash[12..]);
+		Self(address)
+	}
+
+	/// Recovers the address that created `sig` on the 32 byte `hash`.
+	///
+	/// Returns `None` if the signature is invalid.
+	pub fn recover(sig: &[u8; 65], hash: &[u8; 32]) -> Option<Self> {
+		secp256k1_ecdsa_recover(sig, hash)
+			.ok()
+			.map(|pk| Self::from_uncompressed(&pk))
+	}
+}
+
+impl IdentifyAccount for EthAddress {
+	type AccountId = Self;
+
+	fn into_account(self) -> Self {
+		self
+	}
+}
+
+impl From<[u8; 20]> for EthAddress {
+	fn from(address: [u8; 20]) -> Self {
+		Self(address)
+	}
+}
+
+impl AsRef<[u8]> for EthAddress {
+	fn as_ref(&self) -> &[u8] {
+		&self.0
+	}
+}
+
+#[derive(Encode, Decode, MaxEncodedLen, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+/// Ethereum signature in `r ++ s ++ v` format.
+///
+/// The recovery id `v` can be either `0/1` or `27/28`.
+pub struct EthSignature(pub [u8; 65]);
+
+impl Verify for EthSignature {
+	type Signer = EthAddress;
+
+	/// Returns whether `signer` signed the [eth_message_hash] of `msg`.
+	fn verify<L: Lazy<[u8]>>(&self, mut msg: L, signer: &EthAddress) -> bool {
+		let hash = eth_message_hash(msg.get());
+		EthAddress::recover(&self.0, &hash).as_ref() == Some(signer)
+	}
+}
+
+impl From<[u8; 65]> for EthSignature {
+	fn from(sig: [u8; 65]) -> Self {
+		Self(sig)
+	}
+}
+
+impl From<sp_core::ecdsa::Signature> for EthSignature {
+	fn from(sig: sp_core::ecdsa::Signature) -> Self {
+		Self(sig.into())
+	}
+}
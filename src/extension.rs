@@ -0,0 +1,130 @@
+//  Copyright 2021 PolyCrypt GmbH
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+//! Transaction pool checks of signed Perun calls.
+//!
+//! Add [CheckPerunCall] to the signed extensions of the runtime to reject
+//! [Pallet::dispute], [Pallet::progress] and [Pallet::conclude_final]
+//! transactions that are stale for the registered state of their channel
+//! before they are included and charged. Signatures are only checked on
+//! dispatch to keep the validation cheap. Refutations of disputes that
+//! time out within [Config::UrgentPeriod] get a priority boost of
+//! [Config::UrgentPriority].
+
+use crate::*;
+
+use codec::{Decode, Encode};
+use frame_support::traits::IsSubType;
+use sp_runtime::traits::{DispatchInfoOf, SignedExtension};
+use sp_std::{fmt, marker::PhantomData};
+
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+/// Checks the arguments of signed Perun calls against the registered
+/// state of their channel.
+pub struct CheckPerunCall<T: Config + Send + Sync>(PhantomData<T>);
+
+impl<T: Config + Send + Sync> CheckPerunCall<T> {
+	pub fn new() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<T: Config + Send + Sync> Default for CheckPerunCall<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T: Config + Send + Sync> fmt::Debug for CheckPerunCall<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "CheckPerunCall")
+	}
+}
+
+impl<T: Config + Send + Sync> SignedExtension for CheckPerunCall<T>
+where
+	<T as frame_system::Config>::RuntimeCall: IsSubType<Call<T>>,
+{
+	const IDENTIFIER: &'static str = "CheckPerunCall";
+	type AccountId = T::AccountId;
+	type Call = <T as frame_system::Config>::RuntimeCall;
+	type AdditionalSigned = ();
+	type Pre = ();
+
+	fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		_: &DispatchInfoOf<Self::Call>,
+		_: usize,
+	) -> TransactionValidity {
+		let priority = match call.is_sub_type() {
+			Some(Call::dispute { params, state, .. }) => {
+				Pallet::<T>::check_channel(params, state).map_err(Pallet::<T>::invalid)?;
+				if state.finalized {
+					return Err(Pallet::<T>::invalid(Error::<T>::StateFinal.into()));
+				}
+				let registered = Pallet::<T>::check_dispute_register(Some(who), state)
+					.map_err(Pallet::<T>::invalid)?;
+				Pallet::<T>::urgency(&registered)
+			}
+			Some(Call::progress {
+				params,
+				next,
+				signer,
+				..
+			}) => {
+				Pallet::<T>::check_channel(params, next).map_err(Pallet::<T>::invalid)?;
+				Pallet::<T>::participant(params, *signer)
+					.map_err(|e| Pallet::<T>::invalid(e.into()))?;
+				Pallet::<T>::check_progress_register(params, next).map_err(Pallet::<T>::invalid)?;
+				0
+			}
+			Some(Call::conclude_final { params, state, .. }) => {
+				Pallet::<T>::check_channel(params, state).map_err(Pallet::<T>::invalid)?;
+				if !state.finalized {
+					return Err(Pallet::<T>::invalid(Error::<T>::StateNotFinal.into()));
+				}
+				if let Some(dispute) = <StateRegister<T>>::get(state.channel_id) {
+					if dispute.phase == Phase::Conclude {
+						return InvalidTransaction::Stale.into();
+					}
+				}
+				0
+			}
+			_ => 0,
+		};
+
+		Ok(ValidTransaction {
+			priority,
+			..Default::default()
+		})
+	}
+
+	/// The call is fully checked on dispatch.
+	fn pre_dispatch(
+		self,
+		_: &Self::AccountId,
+		_: &Self::Call,
+		_: &DispatchInfoOf<Self::Call>,
+		_: usize,
+	) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+}
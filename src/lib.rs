@@ -23,25 +23,40 @@
 // Error on broken doc links.
 #![deny(rustdoc::broken_intra_doc_links)]
 
-use crate::types::*;
+use crate::{encoding::ChannelEncoding, types::*, watchtower::*};
 
 #[cfg(feature = "runtime-benchmarks")]
-mod benchmarking;
+pub mod benchmarking;
 pub use pallet::*;
 pub mod weights;
 
+pub mod account;
+pub mod encoding;
+pub mod eth;
+pub mod extension;
+pub mod threshold;
 pub mod types;
+pub mod watchtower;
 
 pub use weights::WeightInfo;
 
 use frame_support::{
 	dispatch::DispatchResult,
 	pallet_prelude::*,
-	traits::{Currency, Get, UnixTime},
+	storage::with_storage_layer,
+	traits::{Currency, ExistenceRequirement, Get, UnixTime, WithdrawReasons},
 	PalletId,
 };
-use frame_system::{ensure_signed, pallet_prelude::*};
-use sp_runtime::traits::{AccountIdConversion, CheckedAdd, IdentifyAccount, Verify};
+use frame_system::{ensure_signed, ensure_signed_or_none, pallet_prelude::*};
+use sp_core::Hasher;
+use sp_runtime::offchain::storage::StorageValueRef;
+use sp_runtime::{
+	traits::{
+		AccountIdConversion, CheckedAdd, CheckedSub, IdentifyAccount, SaturatedConversion,
+		Saturating, Verify, Zero,
+	},
+	PerThing, Perbill,
+};
 use sp_std::{cmp, convert::TryFrom, ops::Range, vec::Vec};
 
 macro_rules! require {
@@ -56,11 +71,7 @@ macro_rules! require {
 pub mod pallet {
 	use super::*;
 	use codec::FullCodec;
-	use frame_support::{
-		dispatch::DispatchResult,
-		traits::{ExistenceRequirement, Get},
-	};
-	use sp_core::ByteArray;
+	use frame_support::{dispatch::DispatchResult, traits::Get};
 	use sp_runtime::traits::{CheckedAdd, Member};
 
 	#[pallet::config]
@@ -77,6 +88,30 @@ pub mod pallet {
 		#[pallet::constant]
 		type MinDeposit: Get<BalanceOf<Self>>;
 
+		/// Bond that a disputer must post in [Pallet::dispute].
+		///
+		/// Is returned when the channel is concluded or resumed and slashed
+		/// if the disputed state is refuted by a higher version.
+		#[pallet::constant]
+		type DisputeBond: Get<BalanceOf<Self>>;
+
+		/// Part of a refuted [Config::DisputeBond] that is paid to the
+		/// refuter. The rest is returned to the disputer.
+		#[pallet::constant]
+		type BondSlash: Get<Perbill>;
+
+		/// Bond that an account must post to become a watcher.
+		///
+		/// Is paid to the registrant if the watcher misses a state, see
+		/// [Pallet::slash_watcher].
+		#[pallet::constant]
+		type WatcherBond: Get<BalanceOf<Self>>;
+
+		/// Duration after the conclusion of a channel in which its watcher
+		/// can be slashed.
+		#[pallet::constant]
+		type WatcherSlashPeriod: Get<SecondsOf<Self>>;
+
 		/// Valid range for the number of participants in a channel.
 		#[pallet::constant]
 		type ParticipantNum: Get<Range<ParticipantIndex>>;
@@ -104,14 +139,45 @@ pub mod pallet {
 		/// Off-Chain signature type.
 		///
 		/// Must be possible to verify that a [Config::PK] created a signature.
+		/// Use [sp_runtime::MultiSignature] to allow each participant to sign
+		/// with its own scheme.
 		type Signature: Encode + Decode + Member + TypeInfo + Verify<Signer = Self::PK>;
 		/// PK of a [Config::Signature].
-		type PK: Encode
-			+ Decode
-			+ Member
-			+ ByteArray
-			+ TypeInfo
-			+ IdentifyAccount<AccountId = Self::PK>;
+		///
+		/// Use [sp_runtime::MultiSigner] to allow each participant to sign
+		/// with its own scheme and [threshold::ThresholdKey] to allow
+		/// k-of-n key sets as participants.
+		type PK: Encode + Decode + Member + TypeInfo + IdentifyAccount;
+
+		/// Maximum number of member keys of a [threshold::ThresholdKey].
+		///
+		/// Each participant signature is weighed as this many signatures.
+		/// Use 1 if the participants are no key sets.
+		#[pallet::constant]
+		type MaxThresholdKeys: Get<u32>;
+
+		/// Signature scheme of the [Config::PK]s that disputes are weighed for.
+		///
+		/// Use [SignatureScheme::Mixed] for [sp_runtime::MultiSigner], which
+		/// charges the most expensive scheme.
+		#[pallet::constant]
+		type SignatureScheme: Get<SignatureScheme>;
+
+		/// Verifies the state signatures of all participants of a channel.
+		///
+		/// The batch verification host functions are no longer provided by
+		/// `sp_io`. Use [types::SequentialVerifier] or the
+		/// `types::Sr25519BatchVerifier` of the `batch-verify` feature.
+		/// The default weights are benchmarked with the sequential verifier,
+		/// a batch verifier only lowers the dispute weights after re-running
+		/// the dispute and `conclude_final` benchmarks with it.
+		type Verifier: BatchVerifier<Self::Signature, Self::PK>;
+
+		/// Resolves participants that are on-chain accounts.
+		///
+		/// Use [account::OnChainAccounts] to allow accounts as participants
+		/// and `()` if all participants sign off-chain.
+		type AccountParticipants: AccountParticipants<Self::PK, Self::AccountId>;
 
 		/// Represent a time duration in seconds.
 		type Seconds: FullCodec + Member + TypeInfo + CheckedAdd + PartialOrd + From<u64>;
@@ -125,6 +191,73 @@ pub mod pallet {
 
 		/// App registry.
 		type AppRegistry: AppRegistry<Self>;
+
+		/// Maximal length of [Params::app_params] in bytes.
+		///
+		/// Longer params cannot be decoded.
+		#[pallet::constant]
+		type MaxAppParamsLen: Get<u32>;
+
+		/// Whether signed messages and funding ids are domain separated.
+		///
+		/// Prepends a [Domain] separator to all signed [State]s and
+		/// [Withdrawal]s and to the preimage of all funding ids.
+		/// Must be disabled for compatibility with go-perun clients that do
+		/// not know about domain separation.
+		#[pallet::constant]
+		type DomainSeparation: Get<bool>;
+
+		/// Encoding of channel ids, funding ids and signed messages.
+		///
+		/// Use [encoding::ScaleEncoding] for Substrate native clients and
+		/// [encoding::AbiEncoding] for go-perun clients that reuse their
+		/// Ethereum backend. The latter is configured with the asset holder
+		/// address that the clients use for the outcome.
+		type Encoding: ChannelEncoding<Self>;
+
+		/// Submits the transactions of the off-chain watchtower.
+		///
+		/// Use [watchtower::SignedWatchtower] to defend the channels in
+		/// [Pallet::watch] with a local account or `()` to disable it.
+		type Watchtower: Watchtower<Self>;
+
+		/// Converts the keys of the benchmarked signature schemes.
+		///
+		/// Use `()` if the participants are sr25519 keys and
+		/// [benchmarking::AllSchemes] for [sp_runtime::MultiSigner].
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: benchmarking::BenchmarkHelper<Self>;
+
+		/// Priority of unsigned [Pallet::dispute], [Pallet::conclude_final]
+		/// and [Pallet::withdraw] transactions in the pool.
+		#[pallet::constant]
+		type UnsignedPriority: Get<TransactionPriority>;
+
+		/// Number of blocks that unsigned transactions stay valid.
+		#[pallet::constant]
+		type UnsignedLongevity: Get<TransactionLongevity>;
+
+		/// Maximum number of unsigned transactions per block, see
+		/// [UnsignedCount].
+		#[pallet::constant]
+		type MaxUnsignedPerBlock: Get<u32>;
+
+		/// Maximum number of disputes in [Expiries].
+		///
+		/// Further disputes are not concluded in `on_idle` and must be
+		/// concluded with [Pallet::conclude].
+		#[pallet::constant]
+		type MaxExpiries: Get<u32>;
+
+		/// Priority boost of refutations of disputes that time out within
+		/// [Config::UrgentPeriod].
+		#[pallet::constant]
+		type UrgentPriority: Get<TransactionPriority>;
+
+		/// Time before the timeout of a dispute in which refutations are
+		/// urgent.
+		#[pallet::constant]
+		type UrgentPeriod: Get<Self::Seconds>;
 	}
 
 	#[pallet::pallet]
@@ -140,12 +273,107 @@ pub mod pallet {
 	pub(super) type Deposits<T: Config> =
 		StorageMap<_, Blake2_128Concat, FundingIdOf<T>, BalanceOf<T>>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn settled)]
+	/// Contains the FundingIds of all concluded channels.
+	///
+	/// Deposits into them are rejected. This ensures that a signed
+	/// [Withdrawal] cannot be replayed to drain a later deposit.
+	pub(super) type Settled<T: Config> = StorageMap<_, Blake2_128Concat, FundingIdOf<T>, ()>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn state_registers)]
 	/// Contains all Disputes and [RegisteredState]s.
 	pub(super) type StateRegister<T: Config> =
 		StorageMap<_, Blake2_128Concat, ChannelIdOf<T>, RegisteredStateOf<T>>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn bonds)]
+	/// Contains the disputer and its bond for the registered state of a
+	/// channel.
+	pub(super) type Bonds<T: Config> =
+		StorageMap<_, Blake2_128Concat, ChannelIdOf<T>, (T::AccountId, BalanceOf<T>)>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn checkpoints)]
+	/// Contains the version of the last checkpoint of a channel.
+	///
+	/// A channel cannot be disputed with a lower version, see
+	/// [Pallet::checkpoint].
+	pub(super) type Checkpoints<T: Config> =
+		StorageMap<_, Blake2_128Concat, ChannelIdOf<T>, VersionOf<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn acknowledgements)]
+	/// Contains the participants that acknowledged the registered state of
+	/// a channel.
+	///
+	/// Is reset whenever the registered state changes, see
+	/// [Pallet::acknowledge].
+	pub(super) type Acknowledgements<T: Config> =
+		StorageMap<_, Blake2_128Concat, ChannelIdOf<T>, Vec<ParticipantIndex>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn equivocators)]
+	/// Contains the participants of a channel that were proven to sign
+	/// conflicting states, see [Pallet::report_equivocation].
+	///
+	/// Removed when the channel is concluded.
+	pub(super) type Equivocators<T: Config> =
+		StorageMap<_, Blake2_128Concat, ChannelIdOf<T>, Vec<ParticipantIndex>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn watchers)]
+	/// Contains all bonded watchers.
+	pub(super) type Watchers<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, WatcherOf<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn hires)]
+	/// Contains the watcher that was hired for a channel.
+	pub(super) type Hires<T: Config> = StorageMap<_, Blake2_128Concat, ChannelIdOf<T>, HireOf<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn expiries)]
+	/// Contains the registered disputes ordered by the time from which
+	/// they can be concluded.
+	///
+	/// Expired disputes are concluded in `on_idle`.
+	pub(super) type Expiries<T: Config> =
+		StorageValue<_, BoundedVec<(SecondsOf<T>, ChannelIdOf<T>), T::MaxExpiries>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn disputed_params)]
+	/// Contains the [Params] of all channels in [Expiries].
+	pub(super) type DisputedParams<T: Config> =
+		StorageMap<_, Blake2_128Concat, ChannelIdOf<T>, ParamsOf<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn unsigned_disputes)]
+	/// Contains the last block in which a channel was disputed with an
+	/// unsigned transaction.
+	///
+	/// Limits unsigned disputes to one per channel and block.
+	pub(super) type UnsignedDisputes<T: Config> =
+		StorageMap<_, Blake2_128Concat, ChannelIdOf<T>, BlockNumberFor<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn unsigned_count)]
+	/// Contains the number of unsigned transactions in a block.
+	///
+	/// Limited to [Config::MaxUnsignedPerBlock] across all channels.
+	pub(super) type UnsignedCount<T: Config> =
+		StorageValue<_, (BlockNumberFor<T>, u32), ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn approvals)]
+	/// Contains the approvals of account participants.
+	///
+	/// Maps an account and the hash of a payload to whether the account
+	/// approved the payload with [Pallet::approve].
+	pub(super) type Approvals<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, T::HashValue, ()>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	/// All events that can be emitted by Pallet function.
@@ -158,6 +386,10 @@ pub mod pallet {
 		/// \[channel_id, state\]
 		Disputed(ChannelIdOf<T>, StateOf<T>),
 
+		/// The bond of a refuted disputer was slashed.
+		/// \[channel_id, disputer, amount\]
+		BondSlashed(ChannelIdOf<T>, T::AccountId, BalanceOf<T>),
+
 		/// A channel was progressed.
 		/// \[channel_id\]
 		Progressed(ChannelIdOf<T>, VersionOf<T>, AppIdOf<T>),
@@ -169,6 +401,46 @@ pub mod pallet {
 		/// A participant withdrew funds from a channel.
 		/// \[funding_id\]
 		Withdrawn(FundingIdOf<T>),
+
+		/// A channel was checkpointed with the given version.
+		/// \[channel_id, version\]
+		Checkpointed(ChannelIdOf<T>, VersionOf<T>),
+
+		/// A disputed channel was resumed with the given version.
+		/// \[channel_id, version\]
+		Resumed(ChannelIdOf<T>, VersionOf<T>),
+
+		/// A participant acknowledged the registered state of a channel.
+		/// \[channel_id, participant_index\]
+		Acknowledged(ChannelIdOf<T>, ParticipantIndex),
+
+		/// A participant signed two different states with the same version.
+		/// \[channel_id, participant_index\]
+		Equivocated(ChannelIdOf<T>, ParticipantIndex),
+
+		/// An account bonded funds to become a watcher.
+		/// \[watcher\]
+		WatcherRegistered(T::AccountId),
+
+		/// A watcher withdrew its bond.
+		/// \[watcher\]
+		WatcherUnregistered(T::AccountId),
+
+		/// A watcher was hired for a channel.
+		/// \[channel_id, watcher, fee\]
+		WatcherHired(ChannelIdOf<T>, T::AccountId, BalanceOf<T>),
+
+		/// A watcher refuted a dispute and was paid its fee.
+		/// \[channel_id, watcher, fee\]
+		WatcherPaid(ChannelIdOf<T>, T::AccountId, BalanceOf<T>),
+
+		/// A watcher missed a state and its bond was slashed.
+		/// \[channel_id, watcher, bond\]
+		WatcherSlashed(ChannelIdOf<T>, T::AccountId, BalanceOf<T>),
+
+		/// An account participant approved a payload.
+		/// \[account, payload_hash\]
+		Approved(T::AccountId, T::HashValue),
 	}
 
 	#[pallet::error]
@@ -183,6 +455,8 @@ pub mod pallet {
 		DisputeActive,
 		/// A state cannot be disputed with a state that has a lower version.
 		DisputeVersionTooLow,
+		/// A checkpoint must have a higher version than the last one.
+		CheckpointVersionTooLow,
 		/// The challenge duration is too large.
 		ChallengeDurationOverflow,
 
@@ -223,8 +497,59 @@ pub mod pallet {
 		InvalidSignatureNum,
 		/// The number of participants did not respect the configured limits.
 		InvalidParticipantNum,
+		/// The participant index is out of range.
+		InvalidParticipantIndex,
+		/// The challenge duration must not be zero.
+		InvalidChallengeDuration,
 		/// The app channel state transition is invalid.
 		InvalidTransition,
+		/// The payouts do not match the participants.
+		InvalidPayouts,
+
+		/// The withdrawal is past its expiry.
+		WithdrawalExpired,
+		/// The withdrawal splits exceed the outcome.
+		InvalidSplit,
+		/// The withdrawal fee exceeds the outcome.
+		FeeTooHigh,
+		/// Unsigned disputes can only refute a registered dispute.
+		UnsignedRegistration,
+		/// The origin is not the relayer of the withdrawal.
+		InvalidRelayer,
+		/// The acknowledged version is not the registered one.
+		AcknowledgementOutdated,
+		/// The states are not different states with the same version.
+		NoEquivocation,
+		/// The account is already a watcher.
+		AlreadyWatcher,
+		/// The referenced watcher could not be found.
+		UnknownWatcher,
+		/// The watcher is still hired for a channel.
+		WatcherHired,
+		/// A watcher is already hired for the channel.
+		ChannelWatched,
+		/// The referenced hire could not be found.
+		UnknownHire,
+		/// The channel concluded with at least the version of the receipt.
+		WatcherNotNegligent,
+		/// The slash period of the watcher is over.
+		SlashPeriodOver,
+		/// The equivocation of the participant was already reported.
+		EquivocationReported,
+		/// The origin is not the registrant of the hire offer.
+		InvalidRegistrant,
+		/// The hire offer is past its end.
+		HireExpired,
+		/// An unsigned withdrawal has a fee.
+		UnsignedFee,
+		/// The channel was already disputed unsigned in this block.
+		UnsignedDisputed,
+		/// Unsigned calls need a channel with deposits.
+		UnsignedUnfunded,
+		/// The block contains [Config::MaxUnsignedPerBlock] unsigned calls.
+		UnsignedLimit,
+		/// A value does not fit into its type in [Config::Encoding].
+		ValueTooLarge,
 
 		/// The referenced deposit could not be found.
 		UnknownDeposit,
@@ -232,6 +557,21 @@ pub mod pallet {
 		UnknownChannel,
 	}
 
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Refutes the disputes of watched channels, see [watchtower].
+		fn offchain_worker(block: BlockNumberFor<T>) {
+			if T::Watchtower::ENABLED {
+				Self::refute_disputes(block);
+			}
+		}
+
+		/// Concludes expired disputes with the remaining weight.
+		fn on_idle(_: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::conclude_expired(remaining_weight)
+		}
+	}
+
 	#[pallet::call]
 	/// Contains all user-facing functions.
 	impl<T: Config> Pallet<T> {
@@ -243,7 +583,8 @@ pub mod pallet {
 		/// is concluded with [Pallet::withdraw].
 		///
 		/// There is no limit on how often or for whom a participant can fund.
-		/// The only restriction is that it must be at least [Config::MinDeposit].
+		/// The only restrictions are that it must be at least [Config::MinDeposit]
+		/// and that the channel must not be concluded.
 		/// Over-funding a channel can result in lost funds.
 		///
 		/// Emits an [Event::Deposited] event on success.
@@ -255,21 +596,53 @@ pub mod pallet {
 			amount: BalanceOf<T>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
-			ensure!(amount >= T::MinDeposit::get(), Error::<T>::DepositTooSmall);
-			// Check that a deposit would not overflow, return on failure.
-			let holding = <Deposits<T>>::get(funding_id).unwrap_or_default();
-			// An overflow here can happen if a user wants to deposit more than he has.
-			let new_holdings = holding
-				.checked_add(&amount)
-				.ok_or(Error::<T>::DepositOverflow)?;
-			// Transfer the funds from the user, return on failure.
-			let account_id = Self::account_id();
-			T::Currency::transfer(&who, &account_id, amount, ExistenceRequirement::KeepAlive)?;
-			// Update the holdings in the deposits map.
-			<Deposits<T>>::insert(funding_id, new_holdings);
-			// Emit the 'Deposited' event.
-			Self::deposit_event(Event::Deposited(funding_id, new_holdings));
-			Ok(())
+			Self::do_deposit(&who, funding_id, amount)
+		}
+
+		/// Deposits funds for a participant into a channel.
+		///
+		/// Same as [Pallet::deposit] but calculates the funding id on-chain
+		/// from the `params` of the channel and the index of the participant.
+		/// This prevents funds from being lost because of a wrong funding id.
+		/// Rejects invalid `params` and concluded channels.
+		///
+		/// Emits an [Event::Deposited] event on success.
+		#[pallet::weight(WeightInfoOf::<T>::deposit_for(params.participants.len() as u32))]
+		#[pallet::call_index(6)]
+		pub fn deposit_for(
+			origin: OriginFor<T>,
+			params: ParamsOf<T>,
+			participant_index: ParticipantIndex,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			// Check that the params would allow for a dispute.
+			Self::validate_params(&params)?;
+			ensure!(
+				T::ParticipantNum::get().contains(&(params.participants.len() as u32)),
+				Error::<T>::InvalidParticipantNum
+			);
+			ensure!(
+				params.challenge_duration > SecondsOf::<T>::from(0),
+				Error::<T>::InvalidChallengeDuration
+			);
+			Self::now()
+				.checked_add(&params.challenge_duration)
+				.ok_or(Error::<T>::ChallengeDurationOverflow)?;
+			let part = usize::try_from(participant_index)
+				.ok()
+				.and_then(|i| params.participants.get(i))
+				.ok_or(Error::<T>::InvalidParticipantIndex)?;
+
+			let channel_id = params.channel_id::<T>()?;
+			if let Some(dispute) = <StateRegister<T>>::get(channel_id) {
+				ensure!(
+					dispute.phase != Phase::Conclude,
+					Error::<T>::AlreadyConcluded
+				);
+			}
+			let funding_id = Self::calc_funding_id(channel_id, part);
+			Self::do_deposit(&who, funding_id, amount)
 		}
 
 		/// Disputes a channel in case of a dishonest participant.
@@ -280,10 +653,20 @@ pub mod pallet {
 		/// with a state that has a higher [State::version].
 		/// A dispute automatically starts a timeout of [Params::challenge_duration]
 		/// and can only be re-disputed while it did not run out.
+		/// The disputer posts a [Config::DisputeBond] which is slashed in
+		/// favor of the next disputer if the state is refuted.
 		/// [Pallet::conclude] can be called to conclude the dispute.
+		/// The state must not have a lower version than the last
+		/// [Pallet::checkpoint] of the channel.
+		/// Can also be submitted as unsigned transaction for a funded
+		/// channel, which posts no bond. It then only refutes a registered
+		/// dispute and burns the slash of the refuted bond. Each channel can
+		/// only be disputed unsigned once per block.
+		/// Refutations of a state that another account bonded are free of
+		/// fees.
 		///
 		/// Emits an [Event::Disputed] event on success.
-		#[pallet::weight(WeightInfoOf::<T>::dispute(
+		#[pallet::weight(Pallet::<T>::dispute_weight(
 			cmp::min(state_sigs.len() as u32, T::ParticipantNum::get().end)))]
 		#[pallet::call_index(1)]
 		pub fn dispute(
@@ -291,19 +674,26 @@ pub mod pallet {
 			params: ParamsOf<T>,
 			state: StateOf<T>,
 			state_sigs: Vec<T::Signature>,
-		) -> DispatchResult {
-			ensure_signed(origin)?;
-			// Final states cannot be disputed.
-			ensure!(!state.finalized, Error::<T>::StateFinal);
-			Self::validate_fully_signed(&params, &state, state_sigs)?;
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed_or_none(origin)?;
+			let registered = Self::check_dispute(who.as_ref(), &params, &state, state_sigs)?;
 			let channel_id = state.channel_id;
+			Self::note_unsigned(who.as_ref())?;
+			if who.is_none() {
+				<UnsignedDisputes<T>>::insert(
+					channel_id,
+					<frame_system::Pallet<T>>::block_number(),
+				);
+			}
 
-			let now = Self::now();
-			match <StateRegister<T>>::get(channel_id) {
+			match registered {
 				None => {
-					let timeout = now
+					let timeout = Self::now()
 						.checked_add(&params.challenge_duration)
 						.ok_or(Error::<T>::ChallengeDurationOverflow)?;
+					Self::post_bond(who.as_ref(), channel_id)?;
+					let expiry = Self::expiry(&params, Phase::Register, timeout.clone());
+					Self::schedule(channel_id, params, expiry);
 					<StateRegister<T>>::insert(
 						channel_id,
 						RegisteredState {
@@ -313,21 +703,17 @@ pub mod pallet {
 						},
 					);
 					Self::deposit_event(Event::Disputed(channel_id, state));
-					Ok(())
+					Ok(().into())
 				}
 				Some(dispute) => {
-					ensure!(
-						dispute.phase == Phase::Register,
-						Error::<T>::RegisterPhaseOver
-					);
-					// Only register a new dispute iff the timeout still runs
-					// a newer version came in.
-					ensure!(
-						state.version > dispute.state.version,
-						Error::<T>::DisputeVersionTooLow
-					);
-					ensure!(now <= dispute.timeout, Error::<T>::DisputeTimedOut);
-
+					// The registered state was stale.
+					let pays = Self::refutation_pays(channel_id, who.as_ref());
+					Self::slash_bond(channel_id, who.as_ref())?;
+					if let Some(who) = &who {
+						Self::pay_watcher(channel_id, who)?;
+					}
+					Self::post_bond(who.as_ref(), channel_id)?;
+					<Acknowledgements<T>>::remove(channel_id);
 					<StateRegister<T>>::insert(
 						channel_id,
 						RegisteredState {
@@ -337,7 +723,7 @@ pub mod pallet {
 						},
 					);
 					Self::deposit_event(Event::Disputed(channel_id, state));
-					Ok(())
+					Ok(pays.into())
 				}
 			}
 		}
@@ -348,7 +734,8 @@ pub mod pallet {
 		/// transition conforms with the app logic.
 		///
 		/// Emits an [Event::Progressed] event on success.
-		#[pallet::weight(WeightInfoOf::<T>::progress::<T>(params))]
+		#[pallet::weight(WeightInfoOf::<T>::progress::<T>(params)
+			.saturating_add(Pallet::<T>::member_sigs_weight(1)))]
 		#[pallet::call_index(2)]
 		pub fn progress(
 			origin: OriginFor<T>,
@@ -358,90 +745,63 @@ pub mod pallet {
 			signer: ParticipantIndex,
 		) -> DispatchResult {
 			// Ensure transaction signed by origin.
-			ensure_signed(origin)?;
-
-			// Ensure `next` signed by signer.
-			Self::validate_signed_by(&params, &next, sig, signer)?;
-
-			// Ensure channel has app.
-			ensure!(params.has_app::<T>(), Error::<T>::NoApp);
-
-			// Check current state.
+			let who = ensure_signed(origin)?;
+			let dispute = Self::check_progress(&who, &params, &next, sig, signer)?;
 			let channel_id = next.channel_id;
-			match <StateRegister<T>>::get(channel_id) {
-				Some(dispute) => {
-					// Ensure correct phase. Must be after dispute timeout and not
-					// concluded.
-					let now = Self::now();
-					match dispute.phase {
-						Phase::Register => ensure!(now >= dispute.timeout, Error::<T>::TooEarly),
-						Phase::Progress => {}
-						Phase::Conclude => return Err(Error::<T>::AlreadyConcluded.into()),
-					}
 
-					// Require valid transition.
-					let current = dispute.state;
-					ensure!(
-						Self::valid_transition(&params, &current, &next, signer),
-						Error::<T>::InvalidTransition,
-					);
-
-					// Update state register.
-					<StateRegister<T>>::insert(
-						channel_id,
-						RegisteredState {
-							phase: Phase::Progress,
-							state: next.clone(),
-							timeout: dispute.timeout + params.challenge_duration,
-						},
-					);
-					Self::deposit_event(Event::Progressed(channel_id, next.version, params.app));
+			// Update state register.
+			let timeout = dispute.timeout + params.challenge_duration.clone();
+			let app = params.app.clone();
+			<Acknowledgements<T>>::remove(channel_id);
+			Self::schedule(channel_id, params, timeout.clone());
+			<StateRegister<T>>::insert(
+				channel_id,
+				RegisteredState {
+					phase: Phase::Progress,
+					state: next.clone(),
+					timeout,
+				},
+			);
+			Self::deposit_event(Event::Progressed(channel_id, next.version, app));
 
-					Ok(())
-				}
-				None => Err(Error::<T>::UnknownChannel.into()),
-			}
+			Ok(())
 		}
 
 		/// Concludes a channel.
 		///
-		/// Can only be called after the dispute period.
+		/// Can only be called after the dispute period or once all
+		/// participants acknowledged the registered state with
+		/// [Pallet::acknowledge].
+		/// The outcome is paid out if the channel has [Params::payouts].
+		/// Concluding a channel that holds deposits is free of fees.
 		///
 		/// Emits an [Event::Concluded] event on success.
-		#[pallet::weight(WeightInfoOf::<T>::conclude(params.participants.len() as u32))]
+		#[pallet::weight(WeightInfoOf::<T>::conclude(params.participants.len() as u32)
+			.saturating_add(Pallet::<T>::payouts_weight(params.payouts.len())))]
 		#[pallet::call_index(3)]
-		pub fn conclude(origin: OriginFor<T>, params: ParamsOf<T>) -> DispatchResult {
+		pub fn conclude(origin: OriginFor<T>, params: ParamsOf<T>) -> DispatchResultWithPostInfo {
 			ensure_signed(origin)?;
-			let channel_id = params.channel_id::<T::Hasher>();
+			let channel_id = params.channel_id::<T>()?;
 			match <StateRegister<T>>::get(channel_id) {
 				Some(dispute) => {
+					// Repeated conclusions are charged.
 					if dispute.phase == Phase::Conclude {
-						return Ok(());
+						return Ok(().into());
 					}
 
 					// Check timeout.
-					let mut timeout = dispute.timeout;
-					if dispute.phase == Phase::Register && params.has_app::<T>() {
-						// Extend timeout for app channels.
-						timeout = timeout + params.challenge_duration;
-					}
+					let timeout = Self::expiry(&params, dispute.phase, dispute.timeout.clone());
+					// The timeout does not apply if all participants acknowledged.
+					let acks = <Acknowledgements<T>>::decode_len(channel_id).unwrap_or_default();
 					let now = Self::now();
-					ensure!(now >= timeout, Error::<T>::ConcludedTooEarly);
-
-					// Set final outcome.
-					Self::push_outcome(channel_id, &params.participants, &dispute.state.balances)?;
-
-					// Set the channel to `concluded`.
-					<StateRegister<T>>::insert(
-						channel_id,
-						RegisteredState {
-							phase: Phase::Conclude,
-							state: dispute.state.clone(),
-							timeout: 0.into(),
-						},
+					ensure!(
+						now >= timeout || acks == params.participants.len(),
+						Error::<T>::ConcludedTooEarly
 					);
-					Self::deposit_event(Event::Concluded(channel_id));
-					Ok(())
+
+					let pays = Self::conclusion_pays(channel_id, &params);
+					Self::conclude_registered(channel_id, &params, dispute)?;
+					Ok(pays.into())
 				}
 				None => Err(Error::<T>::UnknownChannel.into()),
 			}
@@ -453,37 +813,47 @@ pub mod pallet {
 		/// want to close it.
 		/// Can only be called with a finalized state that is signed by
 		/// all participants.
+		/// The outcome is paid out if the channel has [Params::payouts].
+		/// Can also be submitted as unsigned transaction for a funded
+		/// channel.
+		/// Concluding a channel that holds deposits is free of fees.
 		///
 		/// Emits an [Event::Concluded] event on success.
-		#[pallet::weight(WeightInfoOf::<T>::conclude_final(params.participants.len() as u32))]
+		#[pallet::weight(WeightInfoOf::<T>::conclude_final(params.participants.len() as u32)
+			.saturating_add(Pallet::<T>::payouts_weight(params.payouts.len()))
+			.saturating_add(Pallet::<T>::unsigned_weight(params.participants.len() as u32))
+			.saturating_add(Pallet::<T>::member_sigs_weight(params.participants.len() as u32)))]
 		#[pallet::call_index(4)]
 		pub fn conclude_final(
 			origin: OriginFor<T>,
 			params: ParamsOf<T>,
 			state: StateOf<T>,
 			state_sigs: Vec<T::Signature>,
-		) -> DispatchResult {
-			ensure_signed(origin)?;
-			Self::validate_fully_signed(&params, &state, state_sigs)?;
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed_or_none(origin)?;
+			let registered = Self::check_conclude_final(who.as_ref(), &params, &state, state_sigs)?;
 			let channel_id = state.channel_id;
-
-			ensure!(state.finalized, Error::<T>::StateNotFinal);
+			Self::note_unsigned(who.as_ref())?;
 
 			// Check if this channel is being disputed.
-			if let Some(dispute) = <StateRegister<T>>::get(channel_id) {
+			if let Some(dispute) = registered {
 				if dispute.phase == Phase::Conclude {
 					ensure!(
 						dispute.state.version == state.version,
 						Error::<T>::ConcludedWithDifferentVersion
 					);
-					return Ok(());
+					// Repeated conclusions are charged.
+					return Ok(().into());
 				}
 			}
 
 			// Set final outcome.
+			let pays = Self::conclusion_pays(channel_id, &params);
 			Self::push_outcome(channel_id, &params.participants, &state.balances)?;
+			Self::pay_out(channel_id, &params);
 
 			// Set the channel to `concluded`.
+			Self::settle(channel_id)?;
 			<StateRegister<T>>::insert(
 				channel_id,
 				RegisteredState {
@@ -493,7 +863,7 @@ pub mod pallet {
 				},
 			);
 			Self::deposit_event(Event::Concluded(channel_id));
-			Ok(())
+			Ok(pays.into())
 		}
 
 		/// Withdraws funds from a concluded channel.
@@ -501,93 +871,1470 @@ pub mod pallet {
 		/// Can be called by each participant after a channel was concluded to
 		/// withdraw his outcome of the channel.
 		/// This is the counterpart to [Pallet::deposit].
+		/// Fails if the [Withdrawal::expiry] passed.
+		/// The [Withdrawal::fee] is paid to the origin, which must be the
+		/// [Withdrawal::relayer] if set. The [Withdrawal::splits] are paid
+		/// next and the rest is paid to the [Withdrawal::receiver].
+		/// The fee and splits must not exceed the outcome.
+		/// Can also be submitted as unsigned transaction without relayer
+		/// and fee.
+		/// Successful withdrawals are free of transaction fees.
 		///
 		/// Emits an [Event::Withdrawn] event on success.
-		#[pallet::weight(WeightInfoOf::<T>::withdraw())]
+		#[pallet::weight(WeightInfoOf::<T>::withdraw()
+			.saturating_add(Pallet::<T>::splits_weight(withdrawal.splits.len()))
+			.saturating_add(Pallet::<T>::unsigned_weight(0))
+			.saturating_add(Pallet::<T>::member_sigs_weight(1)))]
 		#[pallet::call_index(5)]
 		pub fn withdraw(
 			origin: OriginFor<T>,
 			withdrawal: WithdrawalOf<T>,
 			withdrawal_sig: SigOf<T>,
-		) -> DispatchResult {
-			ensure_signed(origin)?;
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed_or_none(origin)?;
+			Self::note_unsigned(who.as_ref())?;
+			Self::withdraw_deposit(who.as_ref(), withdrawal, withdrawal_sig)?;
+			Ok(Pays::No.into())
+		}
+
+		/// Withdraws the outcomes of several participants of a concluded
+		/// channel in one call.
+		///
+		/// All `withdrawals` must be for `channel_id` and are checked like in
+		/// [Pallet::withdraw]. `sigs` contains the signature of each withdrawal.
+		/// Fails without withdrawing anything if one withdrawal fails.
+		/// Successful withdrawals are free of transaction fees.
+		///
+		/// Emits an [Event::Withdrawn] event for each withdrawal on success.
+		#[pallet::weight(WeightInfoOf::<T>::withdraw_all(
+			cmp::min(withdrawals.len() as u32, T::ParticipantNum::get().end))
+			.saturating_add(Pallet::<T>::splits_weight(
+				withdrawals.iter().map(|w| w.splits.len()).sum()))
+			.saturating_add(Pallet::<T>::member_sigs_weight(
+				cmp::min(withdrawals.len() as u32, T::ParticipantNum::get().end))))]
+		#[pallet::call_index(17)]
+		pub fn withdraw_all(
+			origin: OriginFor<T>,
+			channel_id: ChannelIdOf<T>,
+			withdrawals: Vec<WithdrawalOf<T>>,
+			sigs: Vec<SigOf<T>>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
 			ensure!(
-				withdrawal.validate_sig(&withdrawal_sig),
-				Error::<T>::InvalidSignature
+				T::ParticipantNum::get().contains(&(withdrawals.len() as u32)),
+				Error::<T>::InvalidParticipantNum
 			);
-
-			match <StateRegister<T>>::get(withdrawal.channel_id) {
-				Some(dispute) => {
-					ensure!(dispute.phase == Phase::Conclude, Error::<T>::NotConcluded);
-					let funding_id = Self::calc_funding_id(withdrawal.channel_id, &withdrawal.part);
-					// Get and remove the deposit.
-					match <Deposits<T>>::take(funding_id) {
-						Some(deposit) => {
-							// Transfer funds.
-							let account_id = Self::account_id();
-							T::Currency::transfer(
-								&account_id,
-								&withdrawal.receiver,
-								deposit,
-								ExistenceRequirement::AllowDeath,
-							)?;
-							Self::deposit_event(Event::Withdrawn(funding_id));
-							Ok(())
-						}
-						None => Err(Error::<T>::UnknownDeposit.into()),
-					}
-				}
-				None => Err(Error::<T>::UnknownChannel.into()),
+			ensure!(
+				withdrawals.len() == sigs.len(),
+				Error::<T>::InvalidSignatureNum
+			);
+			for (withdrawal, sig) in withdrawals.into_iter().zip(sigs) {
+				ensure!(
+					withdrawal.channel_id == channel_id,
+					Error::<T>::InvalidChannelId
+				);
+				Self::withdraw_deposit(Some(&who), withdrawal, sig)?;
 			}
+			Ok(Pays::No.into())
 		}
-	}
-}
-
-/// Contains all pallet-facing functions.
-impl<T: Config> Pallet<T> {
-	/// Returns the account of the pallet.
-	/// Cache it if it needed multiple times.
-	fn account_id() -> T::AccountId {
-		T::PalletId::get().into_account_truncating()
-	}
 
-	/// Returns the current time in seconds since
-	/// 00:00:00 UTC on 1 January 1970 (unix).
-	///
-	/// Undefined in the first block.
-	pub fn now() -> SecondsOf<T> {
-		<pallet_timestamp::Pallet<T> as UnixTime>::now()
-			.as_secs()
-			.into()
-	}
+		/// Records a state as checkpoint of a channel.
+		///
+		/// Can only be called with a non-finalized state that is signed by
+		/// all participants and while the channel is not disputed.
+		/// In contrast to [Pallet::dispute] no timeout is started. Later
+		/// disputes must have at least the [State::version] of the checkpoint.
+		/// This protects participants that might go offline later.
+		///
+		/// Emits an [Event::Checkpointed] event on success.
+		#[pallet::weight(Pallet::<T>::checkpoint_weight(
+			cmp::min(state_sigs.len() as u32, T::ParticipantNum::get().end)))]
+		#[pallet::call_index(8)]
+		pub fn checkpoint(
+			origin: OriginFor<T>,
+			params: ParamsOf<T>,
+			state: StateOf<T>,
+			state_sigs: Vec<T::Signature>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			// Final states must be concluded instead.
+			ensure!(!state.finalized, Error::<T>::StateFinal);
+			Self::validate_fully_signed(Some(&who), &params, &state, state_sigs)?;
+			let channel_id = state.channel_id;
 
-	/// Calculates the funding id of a participant in a channel.
-	pub fn calc_funding_id(channel: ChannelIdOf<T>, part: &PkOf<T>) -> FundingIdOf<T> {
-		Funding { channel, part }.id::<HasherOf<T>>()
-	}
+			ensure!(
+				!<StateRegister<T>>::contains_key(channel_id),
+				Error::<T>::DisputeActive
+			);
+			if let Some(version) = <Checkpoints<T>>::get(channel_id) {
+				ensure!(state.version > version, Error::<T>::CheckpointVersionTooLow);
+			}
 
-	/// Pushes the outcome of a channel back into the `Deposits` map.
-	///
-	/// Checks that the sum of withdrawals is smaller or equal to the sum
-	/// of deposits per channel.
-	/// This ensures that the participants cannot withdraw more than they
-	/// initially deposited.
-	fn push_outcome(
-		channel: ChannelIdOf<T>,
-		parts: &[T::PK],
-		outcome: &[BalanceOf<T>],
-	) -> DispatchResult {
-		ensure!(parts.len() == outcome.len(), Error::<T>::InvalidOutcome);
-		// Save all Funding IDs for later.
-		let mut fids = Vec::<FundingIdOf<T>>::default();
-		// Calculate the sums of the outcome and deposit.
-		let mut sum_outcome = BalanceOf::<T>::default();
-		let mut sum_deposit = BalanceOf::<T>::default();
+			<Checkpoints<T>>::insert(channel_id, state.version.clone());
+			Self::deposit_event(Event::Checkpointed(channel_id, state.version));
+			Ok(())
+		}
 
-		for (i, part) in parts.iter().enumerate() {
-			let fid = Self::calc_funding_id(channel, part);
-			fids.push(fid);
-			let deposit = <Deposits<T>>::get(fid).unwrap_or_default();
+		/// Cancels the dispute of a channel and resumes off-chain operation.
+		///
+		/// Can only be called with a non-finalized state that is signed by
+		/// all participants and has a higher [State::version] than the
+		/// registered state of a not yet concluded channel.
+		/// The dispute is removed and the state is recorded as
+		/// [Pallet::checkpoint], so that the channel cannot be disputed with
+		/// an older state again.
+		///
+		/// Emits an [Event::Resumed] event on success.
+		#[pallet::weight(Pallet::<T>::resume_weight(
+			cmp::min(state_sigs.len() as u32, T::ParticipantNum::get().end)))]
+		#[pallet::call_index(9)]
+		pub fn resume(
+			origin: OriginFor<T>,
+			params: ParamsOf<T>,
+			state: StateOf<T>,
+			state_sigs: Vec<T::Signature>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			// Final states must be concluded instead.
+			ensure!(!state.finalized, Error::<T>::StateFinal);
+			Self::validate_fully_signed(Some(&who), &params, &state, state_sigs)?;
+			let channel_id = state.channel_id;
+
+			let dispute = <StateRegister<T>>::get(channel_id).ok_or(Error::<T>::UnknownChannel)?;
+			ensure!(
+				dispute.phase != Phase::Conclude,
+				Error::<T>::AlreadyConcluded
+			);
+			ensure!(
+				state.version > dispute.state.version,
+				Error::<T>::DisputeVersionTooLow
+			);
+
+			Self::release_bond(channel_id)?;
+			Self::unschedule(channel_id);
+			<StateRegister<T>>::remove(channel_id);
+			<Acknowledgements<T>>::remove(channel_id);
+			<Checkpoints<T>>::insert(channel_id, state.version.clone());
+			Self::deposit_event(Event::Resumed(channel_id, state.version));
+			Ok(())
+		}
+
+		/// Acknowledges the registered state of a disputed channel.
+		///
+		/// `sig` is the signature of participant `signer` on an
+		/// [Acknowledgement] of the registered [State::version].
+		/// Once all participants acknowledged, [Pallet::conclude] can be
+		/// called without waiting for the timeout.
+		/// Acknowledgements are reset when the registered state changes.
+		///
+		/// Emits an [Event::Acknowledged] event on success.
+		#[pallet::weight(WeightInfoOf::<T>::acknowledge(
+			cmp::min(params.participants.len() as u32, T::ParticipantNum::get().end))
+			.saturating_add(Pallet::<T>::member_sigs_weight(1)))]
+		#[pallet::call_index(10)]
+		pub fn acknowledge(
+			origin: OriginFor<T>,
+			params: ParamsOf<T>,
+			version: VersionOf<T>,
+			sig: T::Signature,
+			signer: ParticipantIndex,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let channel_id = params.channel_id::<T>()?;
+			let dispute = <StateRegister<T>>::get(channel_id).ok_or(Error::<T>::UnknownChannel)?;
+			ensure!(
+				dispute.phase != Phase::Conclude,
+				Error::<T>::AlreadyConcluded
+			);
+			ensure!(
+				dispute.state.version == version,
+				Error::<T>::AcknowledgementOutdated
+			);
+			let part = usize::try_from(signer)
+				.ok()
+				.and_then(|i| params.participants.get(i))
+				.ok_or(Error::<T>::InvalidParticipantIndex)?;
+			let ack = Acknowledgement {
+				channel_id,
+				version,
+			};
+			ensure!(
+				Self::authorized(
+					Some(&who),
+					&Self::acknowledgement_payload(&ack)?,
+					&sig,
+					part
+				),
+				Error::<T>::InvalidSignature
+			);
+
+			<Acknowledgements<T>>::mutate(channel_id, |acks| {
+				let acks = acks.get_or_insert_with(Vec::new);
+				if !acks.contains(&signer) {
+					acks.push(signer);
+				}
+			});
+			Self::deposit_event(Event::Acknowledged(channel_id, signer));
+			Ok(())
+		}
+
+		/// Reports that participant `signer` signed two conflicting states.
+		///
+		/// Honest participants never sign two different states with the same
+		/// [State::version]. `state_a` and `state_b` must be such states and
+		/// `sig_a` and `sig_b` their signatures by `signer`.
+		/// The outcome of the equivocator is forfeited when the channel is
+		/// concluded and distributed evenly among the other participants.
+		///
+		/// Emits an [Event::Equivocated] event on success.
+		#[pallet::weight(WeightInfoOf::<T>::report_equivocation(
+			cmp::min(params.participants.len() as u32, T::ParticipantNum::get().end))
+			.saturating_add(Pallet::<T>::member_sigs_weight(2)))]
+		#[pallet::call_index(11)]
+		pub fn report_equivocation(
+			origin: OriginFor<T>,
+			params: ParamsOf<T>,
+			state_a: StateOf<T>,
+			sig_a: T::Signature,
+			state_b: StateOf<T>,
+			sig_b: T::Signature,
+			signer: ParticipantIndex,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			Self::validate_params(&params)?;
+			let channel_id = params.channel_id::<T>()?;
+			ensure!(
+				state_a.channel_id == channel_id && state_b.channel_id == channel_id,
+				Error::<T>::InvalidChannelId
+			);
+			ensure!(
+				state_a.version == state_b.version && state_a != state_b,
+				Error::<T>::NoEquivocation
+			);
+			let part = usize::try_from(signer)
+				.ok()
+				.and_then(|i| params.participants.get(i))
+				.ok_or(Error::<T>::InvalidParticipantIndex)?;
+			ensure!(
+				verify_sig(&Self::state_payload(&state_a)?, &sig_a, part)
+					&& verify_sig(&Self::state_payload(&state_b)?, &sig_b, part),
+				Error::<T>::InvalidSignature
+			);
+			if let Some(dispute) = <StateRegister<T>>::get(channel_id) {
+				ensure!(
+					dispute.phase != Phase::Conclude,
+					Error::<T>::AlreadyConcluded
+				);
+			}
+
+			let mut equivocators = <Equivocators<T>>::get(channel_id).unwrap_or_default();
+			ensure!(
+				!equivocators.contains(&signer),
+				Error::<T>::EquivocationReported
+			);
+			equivocators.push(signer);
+			<Equivocators<T>>::insert(channel_id, equivocators);
+			Self::deposit_event(Event::Equivocated(channel_id, signer));
+			Ok(())
+		}
+
+		/// Bonds [Config::WatcherBond] to become a watcher.
+		///
+		/// `key` signs the [WatchReceipt]s of the watcher.
+		///
+		/// Emits an [Event::WatcherRegistered] event on success.
+		#[pallet::weight(WeightInfoOf::<T>::register_watcher())]
+		#[pallet::call_index(12)]
+		pub fn register_watcher(origin: OriginFor<T>, key: PkOf<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				!<Watchers<T>>::contains_key(&who),
+				Error::<T>::AlreadyWatcher
+			);
+			let bond = T::WatcherBond::get();
+			T::Currency::transfer(
+				&who,
+				&Self::account_id(),
+				bond,
+				ExistenceRequirement::KeepAlive,
+			)?;
+			<Watchers<T>>::insert(
+				&who,
+				Watcher {
+					key,
+					bond,
+					hires: 0,
+				},
+			);
+			Self::deposit_event(Event::WatcherRegistered(who));
+			Ok(())
+		}
+
+		/// Returns the bond of a watcher that is not hired anymore.
+		///
+		/// Emits an [Event::WatcherUnregistered] event on success.
+		#[pallet::weight(WeightInfoOf::<T>::unregister_watcher())]
+		#[pallet::call_index(13)]
+		pub fn unregister_watcher(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let watcher = <Watchers<T>>::get(&who).ok_or(Error::<T>::UnknownWatcher)?;
+			ensure!(watcher.hires == 0, Error::<T>::WatcherHired);
+
+			<Watchers<T>>::remove(&who);
+			T::Currency::transfer(
+				&Self::account_id(),
+				&who,
+				watcher.bond,
+				ExistenceRequirement::AllowDeath,
+			)?;
+			Self::deposit_event(Event::WatcherUnregistered(who));
+			Ok(())
+		}
+
+		/// Hires a watcher to defend a channel according to `offer`.
+		///
+		/// The origin must be the registrant of the `offer`. The offer must
+		/// be signed by the watcher with its [Watcher::key] in `watcher_sig`
+		/// and by participant `signer` of the channel in `part_sig`.
+		/// The channel must be funded and not concluded.
+		/// The fee is transferred into the pallet and paid to the watcher
+		/// when it refutes a dispute of the channel with [Pallet::dispute].
+		/// Otherwise it is returned when the channel is concluded or the hire
+		/// is released.
+		/// The registrant sends each new state to the watcher off-chain and
+		/// receives a signed [WatchReceipt] for it.
+		///
+		/// Emits an [Event::WatcherHired] event on success.
+		#[pallet::weight(WeightInfoOf::<T>::hire_watcher(
+			cmp::min(params.participants.len() as u32, T::ParticipantNum::get().end))
+			.saturating_add(Pallet::<T>::member_sigs_weight(2)))]
+		#[pallet::call_index(14)]
+		pub fn hire_watcher(
+			origin: OriginFor<T>,
+			params: ParamsOf<T>,
+			offer: HireOfferOf<T>,
+			signer: ParticipantIndex,
+			part_sig: T::Signature,
+			watcher_sig: T::Signature,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(offer.registrant == who, Error::<T>::InvalidRegistrant);
+			Self::validate_params(&params)?;
+			let channel_id = params.channel_id::<T>()?;
+			ensure!(offer.channel_id == channel_id, Error::<T>::InvalidChannelId);
+			ensure!(Self::now() < offer.until, Error::<T>::HireExpired);
+			// The watcher and a participant agreed to the offer.
+			let part = usize::try_from(signer)
+				.ok()
+				.and_then(|i| params.participants.get(i))
+				.ok_or(Error::<T>::InvalidParticipantIndex)?;
+			let payload = Self::hire_offer_payload(&offer)?;
+			ensure!(
+				Self::authorized(Some(&who), &payload, &part_sig, part),
+				Error::<T>::InvalidSignature
+			);
+			let mut watcher =
+				<Watchers<T>>::get(&offer.watcher).ok_or(Error::<T>::UnknownWatcher)?;
+			ensure!(
+				verify_sig(&payload, &watcher_sig, &watcher.key),
+				Error::<T>::InvalidSignature
+			);
+			// The channel exists and is not concluded.
+			ensure!(
+				params
+					.participants
+					.iter()
+					.any(|part| <Deposits<T>>::contains_key(Self::calc_funding_id(
+						channel_id, part
+					))),
+				Error::<T>::UnknownChannel
+			);
+			if let Some(dispute) = <StateRegister<T>>::get(channel_id) {
+				ensure!(
+					dispute.phase != Phase::Conclude,
+					Error::<T>::AlreadyConcluded
+				);
+			}
+			ensure!(
+				!<Hires<T>>::contains_key(channel_id),
+				Error::<T>::ChannelWatched
+			);
+
+			T::Currency::transfer(
+				&who,
+				&Self::account_id(),
+				offer.fee,
+				ExistenceRequirement::KeepAlive,
+			)?;
+			watcher.hires += 1;
+			<Watchers<T>>::insert(&offer.watcher, watcher);
+			<Hires<T>>::insert(
+				channel_id,
+				Hire {
+					registrant: who,
+					watcher: offer.watcher.clone(),
+					fee: offer.fee,
+					until: offer.until,
+					concluded: None,
+				},
+			);
+			Self::deposit_event(Event::WatcherHired(channel_id, offer.watcher, offer.fee));
+			Ok(())
+		}
+
+		/// Ends the hire of a channel.
+		///
+		/// The hire of a concluded channel ends after its slash period.
+		/// The hire of a channel that was never disputed ends after
+		/// [Hire::until] and the fee is returned to the registrant.
+		/// Can be called by anyone to free the watcher.
+		#[pallet::weight(WeightInfoOf::<T>::release_hire())]
+		#[pallet::call_index(15)]
+		pub fn release_hire(origin: OriginFor<T>, channel_id: ChannelIdOf<T>) -> DispatchResult {
+			ensure_signed(origin)?;
+			let hire = <Hires<T>>::get(channel_id).ok_or(Error::<T>::UnknownHire)?;
+			match hire.concluded {
+				Some(concluded) => {
+					let end = concluded
+						.checked_add(&T::WatcherSlashPeriod::get())
+						.ok_or(Error::<T>::ChallengeDurationOverflow)?;
+					ensure!(Self::now() > end, Error::<T>::TooEarly);
+				}
+				None => {
+					ensure!(Self::now() > hire.until, Error::<T>::TooEarly);
+					ensure!(
+						!<StateRegister<T>>::contains_key(channel_id),
+						Error::<T>::DisputeActive
+					);
+					T::Currency::transfer(
+						&Self::account_id(),
+						&hire.registrant,
+						hire.fee,
+						ExistenceRequirement::AllowDeath,
+					)?;
+				}
+			}
+
+			<Hires<T>>::remove(channel_id);
+			Self::unhire(&hire.watcher);
+			Ok(())
+		}
+
+		/// Slashes the watcher of a channel that missed a state.
+		///
+		/// `receipt` must be signed by the watcher and have a higher version
+		/// than the state that the channel was concluded with.
+		/// The bond of the watcher is paid to the registrant of the channel
+		/// and the watcher is removed.
+		///
+		/// Emits an [Event::WatcherSlashed] event on success.
+		#[pallet::weight(WeightInfoOf::<T>::slash_watcher()
+			.saturating_add(Pallet::<T>::member_sigs_weight(1)))]
+		#[pallet::call_index(16)]
+		pub fn slash_watcher(
+			origin: OriginFor<T>,
+			receipt: WatchReceiptOf<T>,
+			sig: T::Signature,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			let channel_id = receipt.channel_id;
+			let hire = <Hires<T>>::get(channel_id).ok_or(Error::<T>::UnknownHire)?;
+			let concluded = hire.concluded.ok_or(Error::<T>::NotConcluded)?;
+			let end = concluded
+				.checked_add(&T::WatcherSlashPeriod::get())
+				.ok_or(Error::<T>::ChallengeDurationOverflow)?;
+			ensure!(Self::now() <= end, Error::<T>::SlashPeriodOver);
+			let registered =
+				<StateRegister<T>>::get(channel_id).ok_or(Error::<T>::UnknownChannel)?;
+			ensure!(
+				receipt.version > registered.state.version,
+				Error::<T>::WatcherNotNegligent
+			);
+			let watcher = <Watchers<T>>::get(&hire.watcher).ok_or(Error::<T>::UnknownWatcher)?;
+			ensure!(
+				verify_sig(&Self::receipt_payload(&receipt)?, &sig, &watcher.key),
+				Error::<T>::InvalidSignature
+			);
+
+			<Hires<T>>::remove(channel_id);
+			<Watchers<T>>::remove(&hire.watcher);
+			T::Currency::transfer(
+				&Self::account_id(),
+				&hire.registrant,
+				watcher.bond,
+				ExistenceRequirement::AllowDeath,
+			)?;
+			Self::deposit_event(Event::WatcherSlashed(
+				channel_id,
+				hire.watcher,
+				watcher.bond,
+			));
+			Ok(())
+		}
+
+		/// Approves a payload on behalf of an account participant.
+		///
+		/// The `payload_hash` is the [Config::Hasher] hash of a payload from
+		/// [Pallet::state_payload] or [Pallet::withdrawal_payload].
+		/// An approval replaces the signature of the origin in all calls
+		/// where it is an account participant, see [Config::AccountParticipants].
+		///
+		/// Emits an [Event::Approved] event on success.
+		#[pallet::weight(WeightInfoOf::<T>::approve())]
+		#[pallet::call_index(7)]
+		pub fn approve(origin: OriginFor<T>, payload_hash: T::HashValue) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			<Approvals<T>>::insert(&who, payload_hash, ());
+			Self::deposit_event(Event::Approved(who, payload_hash));
+			Ok(())
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		/// Accepts unsigned [Pallet::dispute], [Pallet::conclude_final] and
+		/// [Pallet::withdraw] calls that would succeed when dispatched.
+		///
+		/// Unsigned disputes provide a tag per channel and only the first
+		/// one per channel and block succeeds, see [UnsignedDisputes].
+		/// Unsigned disputes and final conclusions need a funded channel and
+		/// unsigned disputes cannot register the first state of a channel.
+		/// At most [Config::MaxUnsignedPerBlock] unsigned calls are accepted
+		/// per block.
+		///
+		/// The participant signatures authorize these calls, so they need
+		/// no signed origin to pay for them.
+		fn validate_unsigned(_: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			if Self::unsigned_in_block() >= T::MaxUnsignedPerBlock::get() {
+				return InvalidTransaction::ExhaustsResources.into();
+			}
+			let mut priority = T::UnsignedPriority::get();
+			let tag = match call {
+				Call::dispute {
+					params,
+					state,
+					state_sigs,
+				} => {
+					let registered = Self::check_dispute(None, params, state, state_sigs.clone())
+						.map_err(Self::invalid)?;
+					priority = priority.saturating_add(Self::urgency(&registered));
+					// Only one unsigned dispute per channel is pooled.
+					(b"dispute", state.channel_id).encode()
+				}
+				Call::conclude_final {
+					params,
+					state,
+					state_sigs,
+				} => {
+					let registered =
+						Self::check_conclude_final(None, params, state, state_sigs.clone())
+							.map_err(Self::invalid)?;
+					if let Some(dispute) = registered {
+						if dispute.phase == Phase::Conclude {
+							return InvalidTransaction::Stale.into();
+						}
+					}
+					(b"conclude", state.channel_id).encode()
+				}
+				Call::withdraw {
+					withdrawal,
+					withdrawal_sig,
+				} => {
+					if !withdrawal.fee.is_zero() {
+						return Err(Self::invalid(Error::<T>::UnsignedFee.into()));
+					}
+					Self::check_withdrawal(None, withdrawal, withdrawal_sig)
+						.map_err(Self::invalid)?
+						.encode()
+				}
+				_ => return InvalidTransaction::Call.into(),
+			};
+
+			ValidTransaction::with_tag_prefix("Perun")
+				.priority(priority)
+				.longevity(T::UnsignedLongevity::get())
+				.and_provides(tag)
+				.propagate(true)
+				.build()
+		}
+	}
+}
+
+impl<T: Config> From<encoding::ValueTooLarge> for Error<T> {
+	fn from(_: encoding::ValueTooLarge) -> Self {
+		Error::<T>::ValueTooLarge
+	}
+}
+
+/// Contains all pallet-facing functions.
+impl<T: Config> Pallet<T> {
+	/// Returns the account of the pallet.
+	/// Cache it if it needed multiple times.
+	fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account_truncating()
+	}
+
+	/// Returns the current time in seconds since
+	/// 00:00:00 UTC on 1 January 1970 (unix).
+	///
+	/// Undefined in the first block.
+	pub fn now() -> SecondsOf<T> {
+		<pallet_timestamp::Pallet<T> as UnixTime>::now()
+			.as_secs()
+			.into()
+	}
+
+	/// Transfers `amount` from `who` into the deposit of `funding_id`.
+	fn do_deposit(
+		who: &T::AccountId,
+		funding_id: FundingIdOf<T>,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		ensure!(amount >= T::MinDeposit::get(), Error::<T>::DepositTooSmall);
+		ensure!(
+			!<Settled<T>>::contains_key(funding_id),
+			Error::<T>::AlreadyConcluded
+		);
+		// Check that a deposit would not overflow, return on failure.
+		let holding = <Deposits<T>>::get(funding_id).unwrap_or_default();
+		// An overflow here can happen if a user wants to deposit more than he has.
+		let new_holdings = holding
+			.checked_add(&amount)
+			.ok_or(Error::<T>::DepositOverflow)?;
+		// Transfer the funds from the user, return on failure.
+		let account_id = Self::account_id();
+		T::Currency::transfer(who, &account_id, amount, ExistenceRequirement::KeepAlive)?;
+		// Update the holdings in the deposits map.
+		<Deposits<T>>::insert(funding_id, new_holdings);
+		// Emit the 'Deposited' event.
+		Self::deposit_event(Event::Deposited(funding_id, new_holdings));
+		Ok(())
+	}
+
+	/// Transfers the [Config::DisputeBond] from `who` into the pallet and
+	/// records it for `channel`.
+	fn post_bond(who: Option<&T::AccountId>, channel: ChannelIdOf<T>) -> DispatchResult {
+		let bond = T::DisputeBond::get();
+		// Unsigned disputes do not post a bond.
+		let who = match who {
+			Some(who) if !bond.is_zero() => who,
+			_ => return Ok(()),
+		};
+		T::Currency::transfer(
+			who,
+			&Self::account_id(),
+			bond,
+			ExistenceRequirement::KeepAlive,
+		)?;
+		<Bonds<T>>::insert(channel, (who.clone(), bond));
+		Ok(())
+	}
+
+	/// Slashes the bond of `channel` since its state was refuted.
+	///
+	/// Pays the [Config::BondSlash] part to the `refuter` and returns the
+	/// rest to the disputer. The slashed part is burned if the refutation
+	/// is unsigned.
+	fn slash_bond(channel: ChannelIdOf<T>, refuter: Option<&T::AccountId>) -> DispatchResult {
+		if let Some((disputer, bond)) = <Bonds<T>>::take(channel) {
+			let slash = T::BondSlash::get().mul_floor(bond);
+			let account_id = Self::account_id();
+			match refuter {
+				Some(refuter) => T::Currency::transfer(
+					&account_id,
+					refuter,
+					slash,
+					ExistenceRequirement::AllowDeath,
+				)?,
+				None => drop(T::Currency::withdraw(
+					&account_id,
+					slash,
+					WithdrawReasons::TRANSFER,
+					ExistenceRequirement::AllowDeath,
+				)?),
+			}
+			T::Currency::transfer(
+				&account_id,
+				&disputer,
+				bond - slash,
+				ExistenceRequirement::AllowDeath,
+			)?;
+			Self::deposit_event(Event::BondSlashed(channel, disputer, slash));
+		}
+		Ok(())
+	}
+
+	/// Returns the bond of `channel` to its disputer.
+	fn release_bond(channel: ChannelIdOf<T>) -> DispatchResult {
+		if let Some((disputer, bond)) = <Bonds<T>>::take(channel) {
+			T::Currency::transfer(
+				&Self::account_id(),
+				&disputer,
+				bond,
+				ExistenceRequirement::AllowDeath,
+			)?;
+		}
+		Ok(())
+	}
+
+	/// Concludes the channel with its registered state of `dispute`.
+	///
+	/// Emits an [Event::Concluded] event on success.
+	fn conclude_registered(
+		channel_id: ChannelIdOf<T>,
+		params: &ParamsOf<T>,
+		dispute: RegisteredStateOf<T>,
+	) -> DispatchResult {
+		// Set final outcome.
+		Self::push_outcome(channel_id, &params.participants, &dispute.state.balances)?;
+		Self::pay_out(channel_id, params);
+
+		// Set the channel to `concluded`.
+		Self::settle(channel_id)?;
+		<StateRegister<T>>::insert(
+			channel_id,
+			RegisteredState {
+				phase: Phase::Conclude,
+				state: dispute.state,
+				timeout: 0.into(),
+			},
+		);
+		Self::deposit_event(Event::Concluded(channel_id));
+		Ok(())
+	}
+
+	/// Concludes the expired disputes in [Expiries] in order until
+	/// `limit` is used up.
+	///
+	/// Returns the consumed weight.
+	fn conclude_expired(limit: Weight) -> Weight {
+		let db = T::DbWeight::get();
+		let now = Self::now();
+		let mut used = Weight::zero();
+		loop {
+			// Reads the expiries, params and registered state and removes
+			// the channel from the expiries if it cannot be concluded.
+			let read = used.saturating_add(db.reads_writes(3, 2));
+			if !limit.all_gte(read) {
+				return used;
+			}
+			used = read;
+			let (expiry, channel_id) = match <Expiries<T>>::get().first() {
+				Some(first) => first.clone(),
+				None => return used,
+			};
+			if expiry > now {
+				return used;
+			}
+			let (params, dispute) = match (
+				<DisputedParams<T>>::get(channel_id),
+				<StateRegister<T>>::get(channel_id),
+			) {
+				(Some(params), Some(dispute)) => (params, dispute),
+				_ => {
+					Self::unschedule(channel_id);
+					continue;
+				}
+			};
+			let conclude = used
+				.saturating_add(WeightInfoOf::<T>::conclude(params.participants.len() as u32))
+				.saturating_add(Self::payouts_weight(params.payouts.len()))
+				.saturating_add(Self::settle_weight());
+			if !limit.all_gte(conclude) {
+				return used;
+			}
+			used = conclude;
+			let result =
+				with_storage_layer(|| Self::conclude_registered(channel_id, &params, dispute));
+			if result.is_err() {
+				// Failed disputes must be concluded manually.
+				Self::unschedule(channel_id);
+			}
+		}
+	}
+
+	/// Returns the time from which a registered state with `timeout` in
+	/// `phase` can be concluded.
+	fn expiry(params: &ParamsOf<T>, phase: Phase, timeout: SecondsOf<T>) -> SecondsOf<T> {
+		if phase == Phase::Register && params.has_app::<T>() {
+			// Extend timeout for app channels.
+			return timeout + params.challenge_duration.clone();
+		}
+		timeout
+	}
+
+	/// Inserts `channel` into [Expiries] at `expiry`.
+	///
+	/// The channel is not scheduled if [Config::MaxExpiries] disputes
+	/// already are.
+	fn schedule(channel: ChannelIdOf<T>, params: ParamsOf<T>, expiry: SecondsOf<T>) {
+		let scheduled = <Expiries<T>>::mutate(|expiries| {
+			expiries.retain(|(_, id)| *id != channel);
+			let pos = expiries.partition_point(|(other, _)| *other <= expiry);
+			expiries.try_insert(pos, (expiry, channel)).is_ok()
+		});
+		if scheduled {
+			<DisputedParams<T>>::insert(channel, params);
+		} else {
+			<DisputedParams<T>>::remove(channel);
+		}
+	}
+
+	/// Removes `channel` from [Expiries].
+	fn unschedule(channel: ChannelIdOf<T>) {
+		<Expiries<T>>::mutate(|expiries| expiries.retain(|(_, id)| *id != channel));
+		<DisputedParams<T>>::remove(channel);
+	}
+
+	/// Transfers the outcome of `withdrawal.part` to the splits and the
+	/// `withdrawal.receiver`.
+	///
+	/// The fee is paid to the signed `origin`.
+	fn withdraw_deposit(
+		origin: Option<&T::AccountId>,
+		withdrawal: WithdrawalOf<T>,
+		sig: SigOf<T>,
+	) -> DispatchResult {
+		let funding_id = Self::check_withdrawal(origin, &withdrawal, &sig)?;
+		// Get and remove the deposit.
+		let deposit = <Deposits<T>>::take(funding_id).ok_or(Error::<T>::UnknownDeposit)?;
+		// Transfer funds.
+		let account_id = Self::account_id();
+		let mut rest = deposit;
+		if let Some(origin) = origin {
+			rest = rest
+				.checked_sub(&withdrawal.fee)
+				.ok_or(Error::<T>::FeeTooHigh)?;
+			T::Currency::transfer(
+				&account_id,
+				origin,
+				withdrawal.fee,
+				ExistenceRequirement::AllowDeath,
+			)?;
+		}
+		for (account, amount) in withdrawal.splits.iter() {
+			rest = rest.checked_sub(amount).ok_or(Error::<T>::InvalidSplit)?;
+			T::Currency::transfer(
+				&account_id,
+				account,
+				*amount,
+				ExistenceRequirement::AllowDeath,
+			)?;
+		}
+		T::Currency::transfer(
+			&account_id,
+			&withdrawal.receiver,
+			rest,
+			ExistenceRequirement::AllowDeath,
+		)?;
+		Self::deposit_event(Event::Withdrawn(funding_id));
+		Ok(())
+	}
+
+	/// Checks that `sig` authorizes `withdrawal` for `origin` and that the
+	/// channel is concluded.
+	///
+	/// Returns the funding id of the withdrawn deposit.
+	fn check_withdrawal(
+		origin: Option<&T::AccountId>,
+		withdrawal: &WithdrawalOf<T>,
+		sig: &SigOf<T>,
+	) -> Result<FundingIdOf<T>, DispatchError> {
+		let funding_id = Self::calc_funding_id(withdrawal.channel_id, &withdrawal.part);
+		// The participant signs the amount that it withdraws.
+		let amount = <Deposits<T>>::get(funding_id).unwrap_or_default();
+		ensure!(
+			Self::authorized(
+				origin,
+				&Self::withdrawal_payload(withdrawal, &amount)?,
+				sig,
+				&withdrawal.part
+			),
+			Error::<T>::InvalidSignature
+		);
+		if let Some(expiry) = &withdrawal.expiry {
+			ensure!(Self::now() <= *expiry, Error::<T>::WithdrawalExpired);
+		}
+		if let Some(relayer) = &withdrawal.relayer {
+			ensure!(origin == Some(relayer), Error::<T>::InvalidRelayer);
+		}
+		// Nobody receives the fee of an unsigned withdrawal.
+		ensure!(
+			origin.is_some() || withdrawal.fee.is_zero(),
+			Error::<T>::UnsignedFee
+		);
+
+		let dispute =
+			<StateRegister<T>>::get(withdrawal.channel_id).ok_or(Error::<T>::UnknownChannel)?;
+		ensure!(dispute.phase == Phase::Conclude, Error::<T>::NotConcluded);
+		ensure!(
+			<Deposits<T>>::contains_key(funding_id),
+			Error::<T>::UnknownDeposit
+		);
+		Ok(funding_id)
+	}
+
+	/// Checks whether `state` can be registered with [Pallet::dispute].
+	///
+	/// Returns the currently registered state of the channel.
+	fn check_dispute(
+		origin: Option<&T::AccountId>,
+		params: &ParamsOf<T>,
+		state: &StateOf<T>,
+		state_sigs: Vec<SigOf<T>>,
+	) -> Result<Option<RegisteredStateOf<T>>, DispatchError> {
+		// Final states cannot be disputed.
+		ensure!(!state.finalized, Error::<T>::StateFinal);
+		let channel_id = state.channel_id;
+		if origin.is_none() {
+			ensure!(
+				<UnsignedDisputes<T>>::get(channel_id)
+					!= Some(<frame_system::Pallet<T>>::block_number()),
+				Error::<T>::UnsignedDisputed
+			);
+			ensure!(
+				Self::funded(channel_id, params),
+				Error::<T>::UnsignedUnfunded
+			);
+		}
+		Self::validate_fully_signed(origin, params, state, state_sigs)?;
+		Self::check_dispute_register(origin, state)
+	}
+
+	/// Checks whether `state` can replace the registered state of its
+	/// channel without checking its signatures.
+	///
+	/// Returns the currently registered state of the channel.
+	fn check_dispute_register(
+		origin: Option<&T::AccountId>,
+		state: &StateOf<T>,
+	) -> Result<Option<RegisteredStateOf<T>>, DispatchError> {
+		let channel_id = state.channel_id;
+		// The state must not be older than the last checkpoint.
+		if let Some(version) = <Checkpoints<T>>::get(channel_id) {
+			ensure!(state.version >= version, Error::<T>::DisputeVersionTooLow);
+		}
+
+		let registered = <StateRegister<T>>::get(channel_id);
+		match &registered {
+			None => {
+				// Only signed disputes register a channel, since unsigned
+				// ones are free and cannot post a bond.
+				ensure!(origin.is_some(), Error::<T>::UnsignedRegistration);
+			}
+			Some(dispute) => {
+				ensure!(
+					dispute.phase == Phase::Register,
+					Error::<T>::RegisterPhaseOver
+				);
+				// Only register a new dispute iff the timeout still runs
+				// a newer version came in.
+				ensure!(
+					state.version > dispute.state.version,
+					Error::<T>::DisputeVersionTooLow
+				);
+				ensure!(Self::now() <= dispute.timeout, Error::<T>::DisputeTimedOut);
+			}
+		}
+		Ok(registered)
+	}
+
+	/// Checks whether the registered state can be progressed to `next`
+	/// with [Pallet::progress].
+	///
+	/// Returns the currently registered state of the channel.
+	fn check_progress(
+		origin: &T::AccountId,
+		params: &ParamsOf<T>,
+		next: &StateOf<T>,
+		sig: SigOf<T>,
+		signer: ParticipantIndex,
+	) -> Result<RegisteredStateOf<T>, DispatchError> {
+		// Ensure `next` signed by signer.
+		Self::validate_signed_by(origin, params, next, sig, signer)?;
+		let dispute = Self::check_progress_register(params, next)?;
+
+		// Require valid transition.
+		ensure!(
+			Self::valid_transition(params, &dispute.state, next, signer),
+			Error::<T>::InvalidTransition,
+		);
+		Ok(dispute)
+	}
+
+	/// Checks whether the registered state can be progressed to `next`
+	/// without checking its signature and the app transition.
+	///
+	/// Returns the currently registered state of the channel.
+	fn check_progress_register(
+		params: &ParamsOf<T>,
+		next: &StateOf<T>,
+	) -> Result<RegisteredStateOf<T>, DispatchError> {
+		// Ensure channel has app.
+		ensure!(params.has_app::<T>(), Error::<T>::NoApp);
+
+		// Check current state.
+		let dispute = <StateRegister<T>>::get(next.channel_id).ok_or(Error::<T>::UnknownChannel)?;
+		// Ensure correct phase. Must be after dispute timeout and not
+		// concluded.
+		match dispute.phase {
+			Phase::Register => ensure!(Self::now() >= dispute.timeout, Error::<T>::TooEarly),
+			Phase::Progress => {}
+			Phase::Conclude => return Err(Error::<T>::AlreadyConcluded.into()),
+		}
+		ensure!(
+			next.version == dispute.state.version.clone() + 1.into(),
+			Error::<T>::InvalidTransition
+		);
+		Ok(dispute)
+	}
+
+	/// Returns the priority boost of a transaction that refutes the
+	/// `registered` state, see [Config::UrgentPriority].
+	fn urgency(registered: &Option<RegisteredStateOf<T>>) -> TransactionPriority {
+		match registered {
+			Some(dispute) => {
+				let urgent = Self::now()
+					.checked_add(&T::UrgentPeriod::get())
+					.map_or(true, |deadline| deadline >= dispute.timeout);
+				if urgent {
+					T::UrgentPriority::get()
+				} else {
+					0
+				}
+			}
+			None => 0,
+		}
+	}
+
+	/// Checks whether the channel can be concluded with the final `state`
+	/// with [Pallet::conclude_final].
+	///
+	/// Returns the currently registered state of the channel.
+	fn check_conclude_final(
+		origin: Option<&T::AccountId>,
+		params: &ParamsOf<T>,
+		state: &StateOf<T>,
+		state_sigs: Vec<SigOf<T>>,
+	) -> Result<Option<RegisteredStateOf<T>>, DispatchError> {
+		if origin.is_none() {
+			ensure!(
+				Self::funded(state.channel_id, params),
+				Error::<T>::UnsignedUnfunded
+			);
+		}
+		Self::validate_fully_signed(origin, params, state, state_sigs)?;
+		ensure!(state.finalized, Error::<T>::StateNotFinal);
+		Ok(<StateRegister<T>>::get(state.channel_id))
+	}
+
+	/// Whether the refutation of `channel` by `refuter` pays its fee.
+	///
+	/// Only refuting the bond of another account is free, so that nobody
+	/// can refute its own disputes for free.
+	fn refutation_pays(channel: ChannelIdOf<T>, refuter: Option<&T::AccountId>) -> Pays {
+		match (<Bonds<T>>::get(channel), refuter) {
+			(Some((disputer, _)), Some(refuter)) if disputer != *refuter => Pays::No,
+			_ => Pays::Yes,
+		}
+	}
+
+	/// Whether the conclusion of a channel pays its fee.
+	///
+	/// Only concluding a channel that holds deposits is free.
+	fn conclusion_pays(channel: ChannelIdOf<T>, params: &ParamsOf<T>) -> Pays {
+		if Self::funded(channel, params) {
+			Pays::No
+		} else {
+			Pays::Yes
+		}
+	}
+
+	/// Whether a participant of the channel has a non-zero deposit.
+	fn funded(channel_id: ChannelIdOf<T>, params: &ParamsOf<T>) -> bool {
+		params.participants.iter().any(|part| {
+			<Deposits<T>>::get(Self::calc_funding_id(channel_id, part))
+				.map_or(false, |deposit| !deposit.is_zero())
+		})
+	}
+
+	/// Returns the number of unsigned calls in the current block.
+	fn unsigned_in_block() -> u32 {
+		let (block, count) = <UnsignedCount<T>>::get();
+		if block == <frame_system::Pallet<T>>::block_number() {
+			count
+		} else {
+			0
+		}
+	}
+
+	/// Counts the call of `origin` if it is unsigned.
+	///
+	/// Fails if the block already contains [Config::MaxUnsignedPerBlock]
+	/// unsigned calls.
+	fn note_unsigned(origin: Option<&T::AccountId>) -> DispatchResult {
+		if origin.is_some() {
+			return Ok(());
+		}
+		let count = Self::unsigned_in_block();
+		ensure!(
+			count < T::MaxUnsignedPerBlock::get(),
+			Error::<T>::UnsignedLimit
+		);
+		<UnsignedCount<T>>::put((<frame_system::Pallet<T>>::block_number(), count + 1));
+		Ok(())
+	}
+
+	/// Converts a failed check of an unsigned call into an invalid
+	/// transaction.
+	///
+	/// Module errors are returned as custom error with their index.
+	fn invalid(err: DispatchError) -> TransactionValidityError {
+		match err {
+			DispatchError::Module(err) => InvalidTransaction::Custom(err.error[0]).into(),
+			_ => InvalidTransaction::Call.into(),
+		}
+	}
+
+	/// Transfers the outcome of a concluded channel to its [Params::payouts].
+	///
+	/// Outcomes that cannot be transferred remain in [Deposits] and can be
+	/// withdrawn with [Pallet::withdraw].
+	fn pay_out(channel: ChannelIdOf<T>, params: &ParamsOf<T>) {
+		for (part, payout) in params.participants.iter().zip(params.payouts.iter()) {
+			let funding_id = Self::calc_funding_id(channel, part);
+			let deposit = match <Deposits<T>>::get(funding_id) {
+				Some(deposit) => deposit,
+				None => continue,
+			};
+			let transferred = T::Currency::transfer(
+				&Self::account_id(),
+				payout,
+				deposit,
+				ExistenceRequirement::AllowDeath,
+			);
+			if transferred.is_ok() {
+				<Deposits<T>>::remove(funding_id);
+				Self::deposit_event(Event::Withdrawn(funding_id));
+			}
+		}
+	}
+
+	/// Cleans up after the conclusion of `channel`.
+	///
+	/// Returns the dispute bond, ends the hire of the watcher and removes
+	/// the expiry, checkpoint, acknowledgements and unsigned dispute marker.
+	fn settle(channel: ChannelIdOf<T>) -> DispatchResult {
+		Self::release_bond(channel)?;
+		Self::end_hire(channel)?;
+		Self::unschedule(channel);
+		<Checkpoints<T>>::remove(channel);
+		<Acknowledgements<T>>::remove(channel);
+		<UnsignedDisputes<T>>::remove(channel);
+		Ok(())
+	}
+
+	/// Pays the fee of the watcher of `channel` if it is the `refuter`.
+	///
+	/// The watcher stays hired until the channel is concluded, so that it
+	/// can still be slashed for missing a newer state that it confirmed.
+	fn pay_watcher(channel: ChannelIdOf<T>, refuter: &T::AccountId) -> DispatchResult {
+		match <Hires<T>>::get(channel) {
+			Some(mut hire) if hire.watcher == *refuter && !hire.fee.is_zero() => {
+				T::Currency::transfer(
+					&Self::account_id(),
+					refuter,
+					hire.fee,
+					ExistenceRequirement::AllowDeath,
+				)?;
+				Self::deposit_event(Event::WatcherPaid(channel, refuter.clone(), hire.fee));
+				hire.fee = Zero::zero();
+				<Hires<T>>::insert(channel, hire);
+				Ok(())
+			}
+			_ => Ok(()),
+		}
+	}
+
+	/// Returns the fee of the watcher of `channel` to the registrant and
+	/// starts the slash period.
+	fn end_hire(channel: ChannelIdOf<T>) -> DispatchResult {
+		if let Some(mut hire) = <Hires<T>>::get(channel) {
+			T::Currency::transfer(
+				&Self::account_id(),
+				&hire.registrant,
+				hire.fee,
+				ExistenceRequirement::AllowDeath,
+			)?;
+			hire.fee = Zero::zero();
+			hire.concluded = Some(Self::now());
+			<Hires<T>>::insert(channel, hire);
+		}
+		Ok(())
+	}
+
+	/// Decrements the number of hires of `watcher`.
+	fn unhire(watcher: &T::AccountId) {
+		<Watchers<T>>::mutate(watcher, |info| {
+			if let Some(info) = info {
+				info.hires = info.hires.saturating_sub(1);
+			}
+		});
+	}
+
+	/// Calculates the funding id of a participant in a channel.
+	pub fn calc_funding_id(channel: ChannelIdOf<T>, part: &PkOf<T>) -> FundingIdOf<T> {
+		let encoded = T::Encoding::encode_funding(&channel, part);
+		T::Hasher::hash(&separated(&Self::domain(MessageTag::Funding), &encoded))
+	}
+
+	/// Returns the encoded [Domain] separator for messages of kind `tag`.
+	///
+	/// Is empty if [Config::DomainSeparation] is disabled.
+	pub fn domain(tag: MessageTag) -> Vec<u8> {
+		if !T::DomainSeparation::get() {
+			return Vec::new();
+		}
+		Domain {
+			prefix: DOMAIN_PREFIX.to_vec(),
+			genesis: <frame_system::Pallet<T>>::block_hash(BlockNumberFor::<T>::zero()),
+			pallet: T::PalletId::get(),
+			tag,
+		}
+		.encode()
+	}
+
+	/// Returns the payload that the participants sign for `state`.
+	pub fn state_payload(state: &StateOf<T>) -> Result<Vec<u8>, Error<T>> {
+		let encoded = T::Encoding::encode_state(state)?;
+		Ok(separated(&Self::domain(MessageTag::State), &encoded))
+	}
+
+	/// Returns the payload that a participant signs for `withdrawal`.
+	///
+	/// `amount` is the outcome of the participant that is withdrawn.
+	pub fn withdrawal_payload(
+		withdrawal: &WithdrawalOf<T>,
+		amount: &BalanceOf<T>,
+	) -> Result<Vec<u8>, Error<T>> {
+		let encoded = T::Encoding::encode_withdrawal(withdrawal, amount)?;
+		Ok(separated(&Self::domain(MessageTag::Withdrawal), &encoded))
+	}
+
+	/// Returns the payload that a watcher and a participant sign for
+	/// `offer`.
+	pub fn hire_offer_payload(offer: &HireOfferOf<T>) -> Result<Vec<u8>, Error<T>> {
+		let encoded = T::Encoding::encode_hire_offer(offer)?;
+		Ok(separated(&Self::domain(MessageTag::HireOffer), &encoded))
+	}
+
+	/// Returns the payload that a watcher signs for `receipt`.
+	pub fn receipt_payload(receipt: &WatchReceiptOf<T>) -> Result<Vec<u8>, Error<T>> {
+		let encoded = T::Encoding::encode_receipt(receipt)?;
+		Ok(separated(&Self::domain(MessageTag::WatchReceipt), &encoded))
+	}
+
+	/// Lets the off-chain watchtower defend the channel of `state`.
+	///
+	/// Replaces the watched state of the channel and adds it to the
+	/// [WATCH_INDEX].
+	/// Must be called in an off-chain context.
+	pub fn watch(params: ParamsOf<T>, state: StateOf<T>, sigs: Vec<SigOf<T>>) {
+		let channel_id = state.channel_id;
+		let key = Self::watch_key(channel_id);
+		StorageValueRef::persistent(&key).set(&WatchedState {
+			params,
+			state,
+			sigs,
+		});
+		let _ = StorageValueRef::persistent(WATCH_INDEX).mutate(
+			|ids: Result<Option<Vec<ChannelIdOf<T>>>, _>| -> Result<_, ()> {
+				let mut ids = ids.ok().flatten().unwrap_or_default();
+				if !ids.contains(&channel_id) {
+					ids.push(channel_id);
+				}
+				Ok(ids)
+			},
+		);
+	}
+
+	/// Returns the local storage key of the [WatchedState] of a channel.
+	pub fn watch_key(channel_id: ChannelIdOf<T>) -> Vec<u8> {
+		let mut key = WATCH_PREFIX.to_vec();
+		channel_id.encode_to(&mut key);
+		key
+	}
+
+	/// Returns the local storage key of the last refutation that was
+	/// submitted for a channel.
+	fn submit_key(channel_id: ChannelIdOf<T>) -> Vec<u8> {
+		let mut key = SUBMIT_PREFIX.to_vec();
+		channel_id.encode_to(&mut key);
+		key
+	}
+
+	/// Submits a refutation for each dispute of a watched channel that
+	/// registered an older state.
+	///
+	/// Only the channels in the [WATCH_INDEX] are checked. Concluded
+	/// channels are removed from it.
+	fn refute_disputes(block: BlockNumberFor<T>) {
+		let now = Self::now();
+		let ids: Vec<ChannelIdOf<T>> = StorageValueRef::persistent(WATCH_INDEX)
+			.get()
+			.ok()
+			.flatten()
+			.unwrap_or_default();
+		let mut concluded = Vec::new();
+		for channel_id in ids {
+			let dispute = match <StateRegister<T>>::get(channel_id) {
+				Some(dispute) if dispute.phase == Phase::Conclude => {
+					concluded.push(channel_id);
+					continue;
+				}
+				Some(dispute) => dispute,
+				None => continue,
+			};
+			let key = Self::watch_key(channel_id);
+			let watched = match StorageValueRef::persistent(&key).get::<WatchedStateOf<T>>() {
+				Ok(Some(watched)) => watched,
+				_ => continue,
+			};
+			let version = watched.state.version.clone();
+			if let Some(call) = Self::refutation(&dispute, watched, &now) {
+				if !Self::lock_submission(channel_id, version, block) {
+					continue;
+				}
+				if !T::Watchtower::submit(call) {
+					// A rejected refutation is retried in the next block.
+					StorageValueRef::persistent(&Self::submit_key(channel_id)).clear();
+				}
+			}
+		}
+		Self::unwatch(&concluded);
+	}
+
+	/// Removes the concluded `channels` from the local storage.
+	fn unwatch(channels: &[ChannelIdOf<T>]) {
+		if channels.is_empty() {
+			return;
+		}
+		for channel_id in channels {
+			StorageValueRef::persistent(&Self::watch_key(*channel_id)).clear();
+			StorageValueRef::persistent(&Self::submit_key(*channel_id)).clear();
+		}
+		let _ = StorageValueRef::persistent(WATCH_INDEX).mutate(
+			|ids: Result<Option<Vec<ChannelIdOf<T>>>, _>| -> Result<_, ()> {
+				let mut ids = ids.ok().flatten().unwrap_or_default();
+				ids.retain(|id| !channels.contains(id));
+				Ok(ids)
+			},
+		);
+	}
+
+	/// Records that a refutation with the watched `version` of a channel is
+	/// submitted in `block`.
+	///
+	/// Returns `false` if it was already submitted within the last
+	/// [Config::UnsignedLongevity] blocks, after which a dropped
+	/// transaction is submitted again.
+	fn lock_submission(
+		channel_id: ChannelIdOf<T>,
+		version: VersionOf<T>,
+		block: BlockNumberFor<T>,
+	) -> bool {
+		let longevity: BlockNumberFor<T> = T::UnsignedLongevity::get().saturated_into();
+		StorageValueRef::persistent(&Self::submit_key(channel_id))
+			.mutate(
+				|submitted: Result<Option<(VersionOf<T>, BlockNumberFor<T>)>, _>| match submitted {
+					Ok(Some((v, at))) if v == version && block < at.saturating_add(longevity) => {
+						Err(())
+					}
+					_ => Ok((version, block)),
+				},
+			)
+			.is_ok()
+	}
+
+	/// Returns the call that replaces the registered state of `dispute`
+	/// with the newer `watched` state.
+	fn refutation(
+		dispute: &RegisteredStateOf<T>,
+		watched: WatchedStateOf<T>,
+		now: &SecondsOf<T>,
+	) -> Option<Call<T>> {
+		let WatchedState {
+			params,
+			state,
+			sigs,
+		} = watched;
+		if dispute.phase == Phase::Conclude || state.version <= dispute.state.version {
+			return None;
+		}
+		if state.finalized {
+			return Some(Call::conclude_final {
+				params,
+				state,
+				state_sigs: sigs,
+			});
+		}
+		if dispute.phase == Phase::Register && *now <= dispute.timeout {
+			return Some(Call::dispute {
+				params,
+				state,
+				state_sigs: sigs,
+			});
+		}
+		if !params.has_app::<T>() {
+			return None;
+		}
+		let signer = (0..sigs.len() as ParticipantIndex)
+			.find(|signer| Self::valid_transition(&params, &dispute.state, &state, *signer))?;
+		let sig = sigs.get(signer as usize)?.clone();
+		Some(Call::progress {
+			params,
+			next: state,
+			sig,
+			signer,
+		})
+	}
+
+	/// Returns the payload that a participant signs for `ack`.
+	pub fn acknowledgement_payload(ack: &AcknowledgementOf<T>) -> Result<Vec<u8>, Error<T>> {
+		let encoded = T::Encoding::encode_acknowledgement(ack)?;
+		Ok(separated(
+			&Self::domain(MessageTag::Acknowledgement),
+			&encoded,
+		))
+	}
+
+	/// Pushes the outcome of a channel back into the `Deposits` map.
+	///
+	/// Checks that the sum of withdrawals is smaller or equal to the sum
+	/// of deposits per channel.
+	/// This ensures that the participants cannot withdraw more than they
+	/// initially deposited.
+	/// The outcome of equivocators is forfeited first, see [Pallet::forfeit].
+	fn push_outcome(
+		channel: ChannelIdOf<T>,
+		parts: &[T::PK],
+		outcome: &[BalanceOf<T>],
+	) -> DispatchResult {
+		ensure!(parts.len() == outcome.len(), Error::<T>::InvalidOutcome);
+		let outcome = &Self::forfeit(channel, outcome)?;
+		// Save all Funding IDs for later.
+		let mut fids = Vec::<FundingIdOf<T>>::default();
+		// Calculate the sums of the outcome and deposit.
+		let mut sum_outcome = BalanceOf::<T>::default();
+		let mut sum_deposit = BalanceOf::<T>::default();
+
+		for (i, part) in parts.iter().enumerate() {
+			let fid = Self::calc_funding_id(channel, part);
+			fids.push(fid);
+			<Settled<T>>::insert(fid, ());
+			let deposit = <Deposits<T>>::get(fid).unwrap_or_default();
 
 			sum_outcome = sum_outcome
 				.checked_add(&outcome[i])
@@ -613,6 +2360,44 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Distributes the outcome of all equivocators of `channel` evenly
+	/// among the other participants.
+	///
+	/// The remainder of the division goes to the first honest participant.
+	/// The outcome is unchanged if all participants equivocated.
+	fn forfeit(
+		channel: ChannelIdOf<T>,
+		outcome: &[BalanceOf<T>],
+	) -> Result<Vec<BalanceOf<T>>, DispatchError> {
+		let mut outcome = outcome.to_vec();
+		let equivocators = <Equivocators<T>>::take(channel).unwrap_or_default();
+		let honest: Vec<usize> = (0..outcome.len())
+			.filter(|i| !equivocators.contains(&(*i as ParticipantIndex)))
+			.collect();
+		if equivocators.is_empty() || honest.is_empty() {
+			return Ok(outcome);
+		}
+
+		let mut forfeited = BalanceOf::<T>::zero();
+		for i in equivocators.iter().map(|i| *i as usize) {
+			if let Some(balance) = outcome.get_mut(i) {
+				forfeited = forfeited
+					.checked_add(balance)
+					.ok_or(Error::<T>::OutcomeOverflow)?;
+				*balance = Zero::zero();
+			}
+		}
+		let num = BalanceOf::<T>::from(honest.len() as u32);
+		let (share, remainder) = (forfeited / num, forfeited % num);
+		for (n, i) in honest.into_iter().enumerate() {
+			let bonus = if n == 0 { share + remainder } else { share };
+			outcome[i] = outcome[i]
+				.checked_add(&bonus)
+				.ok_or(Error::<T>::OutcomeOverflow)?;
+		}
+		Ok(outcome)
+	}
+
 	/// Exposes `push_outcome` for testing only.
 	#[cfg(feature = "expose_privates")]
 	pub fn push_outcome_test(
@@ -623,7 +2408,127 @@ impl<T: Config> Pallet<T> {
 		Self::push_outcome(channel, parts, outcome)
 	}
 
+	/// Weight of [Pallet::dispute] with `p` participants.
+	///
+	/// Weighs the more expensive of a new dispute and a refutation with
+	/// the [Config::SignatureScheme], all member signatures and includes
+	/// the [UnsignedDisputes] marker and the checks of unsigned disputes.
+	fn dispute_weight(p: u32) -> Weight {
+		// A new dispute rewrites a full index of expiries.
+		let sr25519 =
+			WeightInfoOf::<T>::dispute(p, T::MaxExpiries::get()).max(WeightInfoOf::<T>::refute(p));
+		Self::scheme_weight(sr25519, p)
+			.saturating_add(Self::member_sigs_weight(p))
+			.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+			.saturating_add(Self::unsigned_weight(p))
+	}
+
+	/// Weight of [Pallet::checkpoint] with `p` participants.
+	fn checkpoint_weight(p: u32) -> Weight {
+		Self::scheme_weight(WeightInfoOf::<T>::checkpoint(p), p)
+			.saturating_add(Self::member_sigs_weight(p))
+	}
+
+	/// Weight of [Pallet::resume] with `p` participants.
+	///
+	/// Includes the release of the bond and the removal from a full index
+	/// of expiries.
+	fn resume_weight(p: u32) -> Weight {
+		let sr25519 = WeightInfoOf::<T>::resume(p, T::MaxExpiries::get());
+		Self::scheme_weight(sr25519, p).saturating_add(Self::member_sigs_weight(p))
+	}
+
+	/// Weight of [Pallet::dispute] with `p` participants that use the
+	/// [Config::SignatureScheme] and no scheduled disputes.
+	fn scheme_dispute_weight(p: u32) -> Weight {
+		let sr25519 = WeightInfoOf::<T>::dispute(p, 0);
+		let ed25519 = WeightInfoOf::<T>::dispute_ed25519(p, 0);
+		let ecdsa = WeightInfoOf::<T>::dispute_ecdsa(p, 0);
+		match T::SignatureScheme::get() {
+			SignatureScheme::Sr25519 => sr25519,
+			SignatureScheme::Ed25519 => ed25519,
+			SignatureScheme::Ecdsa => ecdsa,
+			SignatureScheme::Mixed => sr25519.max(ed25519).max(ecdsa),
+		}
+	}
+
+	/// Replaces the sr25519 signature verification of a benchmarked
+	/// `weight` with `p` participants by the one of the
+	/// [Config::SignatureScheme].
+	fn scheme_weight(weight: Weight, p: u32) -> Weight {
+		weight
+			.saturating_add(Self::scheme_dispute_weight(p))
+			.saturating_sub(WeightInfoOf::<T>::dispute(p, 0))
+	}
+
+	/// Weight of the additional member signatures of `n` participant
+	/// signatures, see [Config::MaxThresholdKeys].
+	///
+	/// Each member signature is weighed like the signature of a
+	/// participant in [Pallet::dispute].
+	fn member_sigs_weight(n: u32) -> Weight {
+		let members = n.saturating_mul(T::MaxThresholdKeys::get().saturating_sub(1));
+		Self::scheme_dispute_weight(members).saturating_sub(Self::scheme_dispute_weight(0))
+	}
+
+	/// Weight of the checks of unsigned calls for `p` participants.
+	///
+	/// Includes the deposits of the funding check and the [UnsignedCount].
+	fn unsigned_weight(p: u32) -> Weight {
+		T::DbWeight::get().reads_writes(1 + p as u64, 1)
+	}
+
+	/// Weight of transferring `s` [Withdrawal::splits].
+	fn splits_weight(s: usize) -> Weight {
+		T::DbWeight::get()
+			.reads_writes(1, 1)
+			.saturating_mul(s as u64)
+	}
+
+	/// Weight of transferring `p` outcomes to their [Params::payouts].
+	///
+	/// Reads and removes the deposit and transfers it.
+	fn payouts_weight(p: usize) -> Weight {
+		T::DbWeight::get()
+			.reads_writes(2, 2)
+			.saturating_mul(p as u64)
+	}
+
+	/// Weight of [Pallet::settle] in addition to the benchmarked
+	/// conclusion, which neither releases a bond nor ends a hire.
+	///
+	/// Reads and writes the bond, hire, expiries and params, removes the
+	/// unsigned dispute marker and pays back the bond and hire fee.
+	fn settle_weight() -> Weight {
+		T::DbWeight::get().reads_writes(6, 7)
+	}
+
+	/// Checks the params of a channel against the configured limits.
+	fn validate_params(params: &ParamsOf<T>) -> DispatchResult {
+		ensure!(
+			params.payouts.is_empty() || params.payouts.len() == params.participants.len(),
+			Error::<T>::InvalidPayouts
+		);
+		Ok(())
+	}
+
+	/// Returns whether participant `pk` authorized `msg`.
+	///
+	/// An account participant must be the `origin` or have approved `msg`,
+	/// its `sig` is not checked. All other participants need a valid `sig`.
+	/// `origin` is `None` for unsigned calls.
+	fn authorized(origin: Option<&T::AccountId>, msg: &[u8], sig: &SigOf<T>, pk: &PkOf<T>) -> bool {
+		match T::AccountParticipants::account(pk) {
+			Some(account) => {
+				origin == Some(&account)
+					|| <Approvals<T>>::contains_key(&account, T::Hasher::hash(msg))
+			}
+			None => verify_sig(msg, sig, pk),
+		}
+	}
+
 	fn validate_fully_signed(
+		origin: Option<&T::AccountId>,
 		params: &ParamsOf<T>,
 		state: &StateOf<T>,
 		state_sigs: Vec<T::Signature>,
@@ -633,42 +2538,71 @@ impl<T: Config> Pallet<T> {
 			T::ParticipantNum::get().contains(&(state_sigs.len() as u32)),
 			Error::<T>::InvalidParticipantNum
 		);
-		// Check that the State and Params match.
-		let channel_id = params.channel_id::<T::Hasher>();
-		ensure!(state.channel_id == channel_id, Error::<T>::InvalidChannelId);
+		Self::check_channel(params, state)?;
 		// Check the state signatures.
 		ensure!(
 			state_sigs.len() == params.participants.len(),
 			Error::<T>::InvalidSignatureNum
 		);
-		for (i, sig) in state_sigs.iter().enumerate() {
-			ensure!(
-				state.validate_sig(sig, &params.participants[i]),
-				Error::<T>::InvalidSignature
-			);
+		// The payload is encoded once and verified for all participants.
+		let msg = Self::state_payload(state)?;
+		// Account participants are checked on-chain, the others in one batch.
+		let mut sigs = Vec::with_capacity(state_sigs.len());
+		let mut pks = Vec::with_capacity(state_sigs.len());
+		for (sig, pk) in state_sigs.into_iter().zip(params.participants.iter()) {
+			match T::AccountParticipants::account(pk) {
+				Some(_) => ensure!(
+					Self::authorized(origin, &msg, &sig, pk),
+					Error::<T>::InvalidSignature
+				),
+				None => {
+					sigs.push(sig);
+					pks.push(pk.clone());
+				}
+			}
 		}
+		ensure!(
+			T::Verifier::verify_all(&msg, &sigs, &pks),
+			Error::<T>::InvalidSignature
+		);
 		Ok(())
 	}
 
 	fn validate_signed_by(
+		origin: &T::AccountId,
 		params: &ParamsOf<T>,
 		state: &StateOf<T>,
 		sig: T::Signature,
 		signer: ParticipantIndex,
 	) -> DispatchResult {
-		// Check that the State and Params match.
-		let channel_id = params.channel_id::<T::Hasher>();
-		ensure!(state.channel_id == channel_id, Error::<T>::InvalidChannelId);
+		Self::check_channel(params, state)?;
+		let pk = Self::participant(params, signer)?;
 
 		// Check the state signature.
-		let signer_usize = usize::try_from(signer).unwrap();
 		ensure!(
-			state.validate_sig(&sig, &params.participants[signer_usize]),
+			Self::authorized(Some(origin), &Self::state_payload(state)?, &sig, pk),
 			Error::<T>::InvalidSignature
 		);
 		Ok(())
 	}
 
+	/// Checks the params of a channel and that `state` belongs to it.
+	fn check_channel(params: &ParamsOf<T>, state: &StateOf<T>) -> DispatchResult {
+		Self::validate_params(params)?;
+		// Check that the State and Params match.
+		let channel_id = params.channel_id::<T>()?;
+		ensure!(state.channel_id == channel_id, Error::<T>::InvalidChannelId);
+		Ok(())
+	}
+
+	/// Returns the participant with index `signer` in `params`.
+	fn participant(params: &ParamsOf<T>, signer: ParticipantIndex) -> Result<&PkOf<T>, Error<T>> {
+		usize::try_from(signer)
+			.ok()
+			.and_then(|i| params.participants.get(i))
+			.ok_or(Error::<T>::InvalidParticipantIndex)
+	}
+
 	fn valid_transition(
 		params: &ParamsOf<T>,
 		current: &StateOf<T>,
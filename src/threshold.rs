@@ -0,0 +1,111 @@
+//  Copyright 2021 PolyCrypt GmbH
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+//! Threshold (k-of-n) participant keys.
+//!
+//! Use [ThresholdSignature] as [Config::Signature] and [ThresholdKey] as
+//! [Config::PK] to let a group of signers act as one participant.
+//! A participant is then either a single key or a key set of which at
+//! least `threshold` members have to sign.
+//! The funding id of a key set is derived from its encoding and therefore
+//! commits to the threshold and all member keys.
+//! Key sets and signatures have at most `MaxKeys` members, which must be
+//! [Config::MaxThresholdKeys] so that the signature checks are weighed
+//! for all members.
+
+use crate::*;
+
+use codec::{Decode, Encode};
+use frame_support::{CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound};
+use sp_runtime::traits::{IdentifyAccount, Lazy, Verify};
+use sp_std::fmt::Debug;
+
+#[derive(
+	Encode, Decode, CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound, TypeInfo,
+)]
+#[scale_info(skip_type_params(MaxKeys))]
+/// Key of a participant that is either a single key or a k-of-n key set.
+///
+/// Key sets with more than `MaxKeys` members cannot be decoded.
+pub enum ThresholdKey<PK: Clone + Eq + Debug, MaxKeys: Get<u32>> {
+	/// A single signer.
+	Single(PK),
+	/// A group of signers of which at least `threshold` have to sign.
+	Threshold {
+		/// Minimal number of member signatures.
+		threshold: u32,
+		/// Public keys of all members.
+		keys: BoundedVec<PK, MaxKeys>,
+	},
+}
+
+impl<PK: Clone + Eq + Debug, MaxKeys: Get<u32>> IdentifyAccount for ThresholdKey<PK, MaxKeys> {
+	type AccountId = Self;
+
+	fn into_account(self) -> Self {
+		self
+	}
+}
+
+#[derive(
+	Encode, Decode, CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound, TypeInfo,
+)]
+#[scale_info(skip_type_params(MaxKeys))]
+/// Signature of a [ThresholdKey].
+pub enum ThresholdSignature<Sig: Clone + Eq + Debug, MaxKeys: Get<u32>> {
+	/// Signature of a [ThresholdKey::Single].
+	Single(Sig),
+	/// Member signatures for a [ThresholdKey::Threshold].
+	///
+	/// Each signature is paired with the index of its member in
+	/// `keys`. The indices must be strictly increasing.
+	Threshold(BoundedVec<(u32, Sig), MaxKeys>),
+}
+
+impl<Sig, MaxKeys> Verify for ThresholdSignature<Sig, MaxKeys>
+where
+	Sig: Verify + Clone + Eq + Debug,
+	Sig::Signer: Clone + Eq + Debug,
+	MaxKeys: Get<u32>,
+{
+	type Signer = ThresholdKey<Sig::Signer, MaxKeys>;
+
+	/// Returns whether enough distinct members of `signer` signed `msg`.
+	fn verify<L: Lazy<[u8]>>(
+		&self,
+		mut msg: L,
+		signer: &ThresholdKey<Sig::Signer, MaxKeys>,
+	) -> bool {
+		let msg = msg.get();
+		match (self, signer) {
+			(Self::Single(sig), ThresholdKey::Single(pk)) => verify_sig(msg, sig, pk),
+			(Self::Threshold(sigs), ThresholdKey::Threshold { threshold, keys }) => {
+				if *threshold == 0 || sigs.len() < *threshold as usize {
+					return false;
+				}
+				let mut next = 0;
+				for (index, sig) in sigs.iter() {
+					let index = *index as usize;
+					// Rejects duplicate and unordered members.
+					if index < next || !keys.get(index).is_some_and(|pk| verify_sig(msg, sig, pk)) {
+						return false;
+					}
+					next = index + 1;
+				}
+				true
+			}
+			_ => false,
+		}
+	}
+}
@@ -41,19 +41,30 @@ pub type PkOf<T> = <T as pallet::Config>::PK;
 pub type SigOf<T> = <T as pallet::Config>::Signature;
 pub type ParticipantIndex = u32;
 
-pub type ParamsOf<T> = Params<NonceOf<T>, PkOf<T>, SecondsOf<T>, AppIdOf<T>>;
+pub type ParamsOf<T> =
+	Params<NonceOf<T>, PkOf<T>, AccountIdOf<T>, SecondsOf<T>, AppIdOf<T>, AppParamsOf<T>>;
 pub type StateOf<T> = State<ChannelIdOf<T>, VersionOf<T>, BalanceOf<T>>;
 pub type RegisteredStateOf<T> = RegisteredState<StateOf<T>, SecondsOf<T>>;
-pub type WithdrawalOf<T> = Withdrawal<ChannelIdOf<T>, PkOf<T>, AccountIdOf<T>>;
+pub type WithdrawalOf<T> =
+	Withdrawal<ChannelIdOf<T>, PkOf<T>, AccountIdOf<T>, SecondsOf<T>, BalanceOf<T>>;
 pub type FundingOf<T> = Funding<ChannelIdOf<T>, PkOf<T>>;
+pub type AcknowledgementOf<T> = Acknowledgement<ChannelIdOf<T>, VersionOf<T>>;
+pub type WatchReceiptOf<T> = WatchReceipt<ChannelIdOf<T>, VersionOf<T>>;
+pub type WatcherOf<T> = Watcher<PkOf<T>, BalanceOf<T>>;
+pub type HireOf<T> = Hire<AccountIdOf<T>, BalanceOf<T>, SecondsOf<T>>;
+pub type HireOfferOf<T> = HireOffer<ChannelIdOf<T>, AccountIdOf<T>, BalanceOf<T>, SecondsOf<T>>;
 
 pub type AppIdOf<T> = <T as Config>::AppId;
 pub type AppData = Vec<u8>;
+pub type AppParamsOf<T> = BoundedVec<u8, <T as Config>::MaxAppParamsLen>;
 
 pub trait AppId: Encode + Decode + TypeInfo + Member + PartialEq {}
 impl<T: Encode + Decode + TypeInfo + Member + PartialEq> AppId for T {}
 
 pub trait AppRegistry<T: pallet::Config> {
+	/// Returns whether the transition `from` -> `to` is valid for the app.
+	///
+	/// The app configuration is available in [Params::app_params].
 	fn valid_transition(
 		params: &ParamsOf<T>,
 		from: &StateOf<T>,
@@ -69,7 +80,7 @@ pub trait AppRegistry<T: pallet::Config> {
 /// Fixed parameters of a channel.
 ///
 /// The values are agreed on by all participants before opening a channel.
-pub struct Params<Nonce, PK, Seconds, AppId> {
+pub struct Params<Nonce, PK, AccountId, Seconds, AppId, AppParams> {
 	/// Nonce to make these Params unique. Should be picked randomly.
 	pub nonce: Nonce,
 
@@ -81,14 +92,32 @@ pub struct Params<Nonce, PK, Seconds, AppId> {
 
 	// App in channel.
 	pub app: AppId,
+
+	/// Immutable app configuration of the channel.
+	///
+	/// Is committed to by [Params::channel_id] and available to the
+	/// [AppRegistry] through the params, so that it does not need to be
+	/// repeated in every [State::data].
+	/// Its length is bounded by [Config::MaxAppParamsLen], so that longer
+	/// params are already rejected when they are decoded.
+	pub app_params: AppParams,
+
+	/// Accounts that receive the outcome of the participants.
+	///
+	/// Must be empty or contain one account per participant.
+	/// If set, the outcome is paid out on conclusion and the participants
+	/// do not need to call [Pallet::withdraw].
+	pub payouts: Vec<AccountId>,
 }
 
-impl<Nonce, PK, Seconds, AppId> Default for Params<Nonce, PK, Seconds, AppId>
+impl<Nonce, PK, AccountId, Seconds, AppId, AppParams> Default
+	for Params<Nonce, PK, AccountId, Seconds, AppId, AppParams>
 where
 	Nonce: Default,
 	Vec<PK>: Default, // This is crucial for initializing an empty vector of PK
 	Seconds: Default,
 	AppId: Default,
+	AppParams: Default,
 {
 	fn default() -> Self {
 		Self {
@@ -96,6 +125,8 @@ where
 			participants: Vec::default(), // Initialize an empty vector of PK
 			challenge_duration: Seconds::default(),
 			app: AppId::default(),
+			app_params: AppParams::default(),
+			payouts: Vec::default(),
 		}
 	}
 }
@@ -159,7 +190,7 @@ pub struct RegisteredState<State, Seconds> {
 	pub timeout: Seconds,
 }
 
-#[derive(Encode, Decode, Copy, Clone, PartialEq, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug, TypeInfo)]
 #[codec(dumb_trait_bound)]
 /// Withdrawal authorization for on-chain funds.
 ///
@@ -167,7 +198,7 @@ pub struct RegisteredState<State, Seconds> {
 /// on-chain funds withdrawal to a specific on-chain account.
 ///
 /// NOTE: The signature is not part of the struct.
-pub struct Withdrawal<ChannelId, PK, AccountId> {
+pub struct Withdrawal<ChannelId, PK, AccountId, Seconds, Balance> {
 	/// Channel from with to withdraw.
 	pub channel_id: ChannelId,
 
@@ -176,13 +207,33 @@ pub struct Withdrawal<ChannelId, PK, AccountId> {
 
 	/// On-Chain Account to credited.
 	pub receiver: AccountId,
+
+	/// Optional point in time after which the Withdrawal is invalid.
+	pub expiry: Option<Seconds>,
+
+	/// Accounts that are credited a fixed amount of the outcome.
+	///
+	/// The rest of the outcome is credited to the `receiver`.
+	pub splits: Vec<(AccountId, Balance)>,
+
+	/// Fee that is credited to the submitter of the withdrawal.
+	///
+	/// Lets a relayer submit the withdrawal for the participant.
+	pub fee: Balance,
+
+	/// Optional account that must submit the withdrawal.
+	///
+	/// Anyone can submit the withdrawal and receive the fee if unset.
+	pub relayer: Option<AccountId>,
 }
 
-impl<ChannelId, PK, AccountId> Default for Withdrawal<ChannelId, PK, AccountId>
+impl<ChannelId, PK, AccountId, Seconds, Balance> Default
+	for Withdrawal<ChannelId, PK, AccountId, Seconds, Balance>
 where
 	ChannelId: Default,
 	PK: ByteArray + MaxEncodedLen,
 	AccountId: Default,
+	Balance: Default,
 {
 	fn default() -> Self {
 		let array_len = PK::max_encoded_len();
@@ -202,26 +253,189 @@ where
 			channel_id: ChannelId::default(),
 			part,
 			receiver: AccountId::default(),
+			expiry: None,
+			splits: Vec::new(),
+			fee: Balance::default(),
+			relayer: None,
 		}
 	}
 }
 
 #[derive(Encode, Decode, Default, Copy, Clone, PartialEq, RuntimeDebug)]
 #[codec(dumb_trait_bound)]
-/// Funding is exclusively used to calculate funding ids via [Funding::id].
+/// Funding is exclusively used to calculate funding ids with
+/// [crate::encoding::ScaleEncoding].
 pub struct Funding<ChannelId, PK> {
 	pub channel: ChannelId,
 	pub part: PK,
 }
 
-impl<Nonce, PK, Seconds, AppId: crate::AppId> Params<Nonce, PK, Seconds, AppId>
-where
-	Params<Nonce, PK, Seconds, AppId>: Encode,
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug, TypeInfo)]
+#[codec(dumb_trait_bound)]
+/// Acknowledgement of a registered state.
+///
+/// Is signed by a participant that agrees with the registered state of a
+/// dispute. A channel can be concluded without waiting for the timeout once
+/// all participants acknowledged, see [Pallet::acknowledge].
+pub struct Acknowledgement<ChannelId, Version> {
+	/// Channel of the registered state.
+	pub channel_id: ChannelId,
+
+	/// Version of the registered state.
+	pub version: Version,
+}
+
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug, TypeInfo)]
+#[codec(dumb_trait_bound)]
+/// Receipt of a watcher for a state that it has to defend.
+///
+/// Is signed by the watcher with its [Watcher::key] when it receives a
+/// new state from the registrant. Proves that the watcher missed the
+/// state if the channel concludes with a lower version, see
+/// [Pallet::slash_watcher].
+pub struct WatchReceipt<ChannelId, Version> {
+	/// Channel of the state.
+	pub channel_id: ChannelId,
+
+	/// Version of the state.
+	pub version: Version,
+}
+
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug, TypeInfo)]
+#[codec(dumb_trait_bound)]
+/// Bonded watchtower that refutes stale disputes on behalf of participants.
+pub struct Watcher<PK, Balance> {
+	/// Off-chain key that signs [WatchReceipt]s.
+	pub key: PK,
+
+	/// Bond that is slashed if the watcher misses a state.
+	pub bond: Balance,
+
+	/// Number of channels that the watcher is hired for.
+	pub hires: u32,
+}
+
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug, TypeInfo)]
+#[codec(dumb_trait_bound)]
+/// Hire of a [Watcher] for a channel.
+pub struct Hire<AccountId, Balance, Seconds> {
+	/// Account that hired the watcher and paid the fee.
+	pub registrant: AccountId,
+
+	/// Account of the hired watcher.
+	pub watcher: AccountId,
+
+	/// Fee that is paid to the watcher when it refutes a dispute.
+	pub fee: Balance,
+
+	/// End of the hire if the channel is never disputed, see
+	/// [HireOffer::until].
+	pub until: Seconds,
+
+	/// Time at which the channel was concluded.
+	///
+	/// The watcher can be slashed until [Config::WatcherSlashPeriod] after
+	/// this point in time.
+	pub concluded: Option<Seconds>,
+}
+
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug, TypeInfo)]
+#[codec(dumb_trait_bound)]
+/// Offer of a [Watcher] to watch a channel for a registrant.
+///
+/// Is signed by the watcher with its [Watcher::key] and by a participant
+/// of the channel, see [Pallet::hire_watcher].
+///
+/// NOTE: The signatures are not part of the struct.
+pub struct HireOffer<ChannelId, AccountId, Balance, Seconds> {
+	/// Channel to watch.
+	pub channel_id: ChannelId,
+
+	/// Account that hires the watcher and pays the fee.
+	pub registrant: AccountId,
+
+	/// Account of the watcher.
+	pub watcher: AccountId,
+
+	/// Fee that is paid to the watcher when it refutes a dispute.
+	pub fee: Balance,
+
+	/// Point in time until which the channel is watched.
+	///
+	/// The hire can be released afterwards if the channel was never
+	/// disputed, see [Pallet::release_hire].
+	pub until: Seconds,
+}
+
+/// Prefix of every [Domain] separator.
+pub const DOMAIN_PREFIX: &[u8] = b"perun-polkadot";
+
+#[derive(Encode, Decode, Copy, Clone, PartialEq, RuntimeDebug, TypeInfo)]
+/// Signature scheme of the participants, see [Config::SignatureScheme].
+pub enum SignatureScheme {
+	Sr25519,
+	Ed25519,
+	Ecdsa,
+	/// Participants can use any of the schemes.
+	Mixed,
+}
+
+#[derive(Encode, Decode, Copy, Clone, PartialEq, RuntimeDebug, TypeInfo)]
+/// Kind of a message that is signed or hashed by the pallet.
+pub enum MessageTag {
+	/// A [State] that is signed by the participants.
+	State,
+	/// A [Withdrawal] that is signed by a participant.
+	Withdrawal,
+	/// A [Funding] that is hashed into a funding id.
+	Funding,
+	/// An [Acknowledgement] that is signed by a participant.
+	Acknowledgement,
+	/// A [WatchReceipt] that is signed by a watcher.
+	WatchReceipt,
+	/// A [HireOffer] that is signed by a watcher and a participant.
+	HireOffer,
+}
+
+#[derive(Encode, Decode, Copy, Clone, PartialEq, RuntimeDebug, TypeInfo)]
+#[codec(dumb_trait_bound)]
+/// Domain separator that is prepended to signed and hashed messages.
+///
+/// Binds a message to one chain, one pallet instance and one kind of
+/// message, so that it cannot be replayed anywhere else.
+/// Calculated with [Pallet::domain].
+pub struct Domain<Hash> {
+	/// Always [DOMAIN_PREFIX].
+	pub prefix: Vec<u8>,
+
+	/// Hash of the genesis block of the chain.
+	pub genesis: Hash,
+
+	/// ID of the pallet instance.
+	pub pallet: PalletId,
+
+	/// Kind of the message.
+	pub tag: MessageTag,
+}
+
+impl<Nonce, PK, AccountId, Seconds, AppId: crate::AppId, MaxAppParamsLen>
+	Params<Nonce, PK, AccountId, Seconds, AppId, BoundedVec<u8, MaxAppParamsLen>>
 {
 	/// Calculates the Channel ID of the Params.
-	pub fn channel_id<T: Hasher>(&self) -> T::Out {
-		let encoded = Encode::encode(&self);
-		T::hash(&encoded)
+	///
+	/// Hashes the params as encoded by [Config::Encoding].
+	/// Fails if the params cannot be encoded.
+	pub fn channel_id<T>(&self) -> Result<ChannelIdOf<T>, Error<T>>
+	where
+		T: Config<
+				Nonce = Nonce,
+				PK = PK,
+				Seconds = Seconds,
+				AppId = AppId,
+				MaxAppParamsLen = MaxAppParamsLen,
+			> + frame_system::Config<AccountId = AccountId>,
+	{
+		Ok(T::Hasher::hash(&T::Encoding::encode_params(self)?))
 	}
 
 	pub fn has_app<T: Config<AppId = AppId>>(&self) -> bool {
@@ -230,42 +444,153 @@ where
 	}
 }
 
-impl<ChannelId, Version, Balance> State<ChannelId, Version, Balance>
-where
-	State<ChannelId, Version, Balance>: Encode,
+/// Returns the `domain` separator followed by the encoded `msg`.
+pub fn separated(domain: &[u8], msg: &[u8]) -> Vec<u8> {
+	let mut payload = domain.to_vec();
+	payload.extend_from_slice(msg);
+	payload
+}
+
+/// Returns whether `sig` is a valid signature of `msg` and was created by `pk`.
+pub fn verify_sig<Sig: Verify<Signer = PK>, PK: IdentifyAccount + Clone>(
+	msg: &[u8],
+	sig: &Sig,
+	pk: &PK,
+) -> bool {
+	sig.verify(msg, &pk.clone().into_account())
+}
+
+/// Verifies the signatures of all participants on the same message.
+///
+/// Is configured with [Config::Verifier].
+pub trait BatchVerifier<Sig, PK> {
+	/// Returns whether `sigs[i]` is a valid signature of `msg` that was
+	/// created by `pks[i]` for every `i`.
+	///
+	/// Returns `false` if the lengths of `sigs` and `pks` differ.
+	fn verify_all(msg: &[u8], sigs: &[Sig], pks: &[PK]) -> bool;
+}
+
+/// Verifies each signature on its own with [verify_sig].
+pub struct SequentialVerifier;
+
+impl<Sig: Verify<Signer = PK>, PK: IdentifyAccount + Clone> BatchVerifier<Sig, PK>
+	for SequentialVerifier
 {
-	/// Returns whether `sig` is a valid signature for this State and was
-	/// created by `PK`.
-	pub fn validate_sig<Sig: Verify<Signer = PK>, PK: IdentifyAccount<AccountId = PK>>(
-		&self,
-		sig: &Sig,
-		pk: &PK,
+	fn verify_all(msg: &[u8], sigs: &[Sig], pks: &[PK]) -> bool {
+		sigs.len() == pks.len()
+			&& sigs
+				.iter()
+				.zip(pks)
+				.all(|(sig, pk)| verify_sig(msg, sig, pk))
+	}
+}
+
+/// Signing context of substrate sr25519 signatures.
+#[cfg(feature = "batch-verify")]
+const SR25519_CONTEXT: &[u8] = b"substrate";
+
+/// Verifies all sr25519 signatures in one schnorrkel batch.
+///
+/// Signatures of other schemes in a [sp_runtime::MultiSignature] are
+/// verified on their own with [verify_sig].
+/// The batch is verified in the runtime and not by the host, benchmark the
+/// dispute weights with this verifier to see whether it pays off for the
+/// number of participants of the runtime.
+#[cfg(feature = "batch-verify")]
+pub struct Sr25519BatchVerifier;
+
+#[cfg(feature = "batch-verify")]
+impl BatchVerifier<sp_core::sr25519::Signature, sp_core::sr25519::Public> for Sr25519BatchVerifier {
+	fn verify_all(
+		msg: &[u8],
+		sigs: &[sp_core::sr25519::Signature],
+		pks: &[sp_core::sr25519::Public],
 	) -> bool {
-		let msg = Encode::encode(&self);
-		sig.verify(&*msg, pk)
+		sigs.len() == pks.len()
+			&& verify_sr25519_batch(
+				msg,
+				sigs.iter().map(|sig| -> &[u8] { sig.as_ref() }),
+				pks.iter().map(|pk| -> &[u8] { pk.as_ref() }),
+			)
 	}
 }
 
-impl<ChannelId, Pk, AccountId> Withdrawal<ChannelId, Pk, AccountId>
-where
-	Withdrawal<ChannelId, Pk, AccountId>: Encode,
-	Pk: IdentifyAccount<AccountId = Pk>,
-{
-	/// Returns whether `sig` is a valid signature for this Withdrawal
-	/// and was created by the participant that wants to claim the funds.
-	pub fn validate_sig<Sig: Verify<Signer = Pk>>(&self, sig: &Sig) -> bool {
-		let msg = Encode::encode(&self);
-		sig.verify(&*msg, &self.part)
+#[cfg(feature = "batch-verify")]
+impl BatchVerifier<sp_runtime::MultiSignature, sp_runtime::MultiSigner> for Sr25519BatchVerifier {
+	fn verify_all(
+		msg: &[u8],
+		sigs: &[sp_runtime::MultiSignature],
+		pks: &[sp_runtime::MultiSigner],
+	) -> bool {
+		use sp_runtime::{MultiSignature, MultiSigner};
+
+		if sigs.len() != pks.len() {
+			return false;
+		}
+		let mut batch_sigs = Vec::with_capacity(sigs.len());
+		let mut batch_pks = Vec::with_capacity(pks.len());
+		for (sig, pk) in sigs.iter().zip(pks) {
+			match (sig, pk) {
+				(MultiSignature::Sr25519(sig), MultiSigner::Sr25519(pk)) => {
+					batch_sigs.push(AsRef::<[u8]>::as_ref(sig));
+					batch_pks.push(AsRef::<[u8]>::as_ref(pk));
+				}
+				_ => {
+					if !verify_sig(msg, sig, pk) {
+						return false;
+					}
+				}
+			}
+		}
+		verify_sr25519_batch(msg, batch_sigs, batch_pks)
 	}
 }
 
-impl<ChannelId, PK> Funding<ChannelId, PK>
-where
-	Funding<ChannelId, PK>: Encode,
-{
-	/// Calculates the funding id of a participant in a channel.
-	pub fn id<H: Hasher>(&self) -> H::Out {
-		let encoded = Encode::encode(&self);
-		H::hash(&encoded)
+/// Returns whether all `sigs` are valid sr25519 signatures of `msg` by the
+/// respective `pks`.
+///
+/// Uses the deterministic schnorrkel batch verification since the runtime
+/// has no source of randomness.
+#[cfg(feature = "batch-verify")]
+fn verify_sr25519_batch<'a>(
+	msg: &[u8],
+	sigs: impl IntoIterator<Item = &'a [u8]>,
+	pks: impl IntoIterator<Item = &'a [u8]>,
+) -> bool {
+	let sigs = sigs
+		.into_iter()
+		.map(schnorrkel::Signature::from_bytes)
+		.collect::<Result<Vec<_>, _>>();
+	let pks = pks
+		.into_iter()
+		.map(schnorrkel::PublicKey::from_bytes)
+		.collect::<Result<Vec<_>, _>>();
+	let (Ok(sigs), Ok(pks)) = (sigs, pks) else {
+		return false;
+	};
+	if sigs.is_empty() {
+		return true;
+	}
+	let ctx = schnorrkel::signing_context(SR25519_CONTEXT);
+	let transcripts = sigs.iter().map(|_| ctx.bytes(msg));
+	schnorrkel::verify_batch_deterministic(transcripts, &sigs, &pks, false).is_ok()
+}
+
+/// Resolves participants that are on-chain accounts.
+///
+/// Is configured with [Config::AccountParticipants].
+pub trait AccountParticipants<PK, AccountId> {
+	/// Returns the account of `pk` if it is an account participant.
+	///
+	/// An account participant authorizes a message by being the origin of
+	/// the call or with [Pallet::approve] instead of a signature.
+	fn account(pk: &PK) -> Option<AccountId>;
+}
+
+/// All participants sign off-chain.
+impl<PK, AccountId> AccountParticipants<PK, AccountId> for () {
+	fn account(_: &PK) -> Option<AccountId> {
+		None
 	}
 }
@@ -0,0 +1,116 @@
+//  Copyright 2021 PolyCrypt GmbH
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+//! Off-chain watchtower.
+//!
+//! The off-chain worker of the pallet defends channels with the latest
+//! states that are stored in the local off-chain storage of the node.
+//! Whenever a watched channel is disputed with an older state, it submits
+//! a [Pallet::dispute] with the newer state before the timeout runs out.
+//! Once the timeout passed, it submits a [Pallet::progress] for app
+//! channels and a [Pallet::conclude_final] for final states.
+//!
+//! States are added with [Pallet::watch] or by writing an encoded
+//! [WatchedState] to the persistent local storage under
+//! [Pallet::watch_key], e.g. with the `offchain_localStorageSet` RPC.
+//! In the latter case the channel id must also be added to the encoded
+//! list of channel ids under [WATCH_INDEX], since only the channels in it
+//! are checked.
+//! Each refutation is submitted once per watched version and only
+//! submitted again if it was not included after
+//! [Config::UnsignedLongevity] blocks.
+
+use crate::*;
+
+use codec::{Decode, Encode};
+use frame_system::offchain::{
+	AppCrypto, CreateSignedTransaction, SendSignedTransaction, SendTransactionTypes, Signer,
+	SigningTypes, SubmitTransaction,
+};
+use sp_runtime::RuntimeDebug;
+use sp_std::marker::PhantomData;
+
+/// Prefix of the local storage keys of watched channels.
+pub const WATCH_PREFIX: &[u8] = b"perun::watch::";
+/// Local storage key of the list of watched channel ids.
+pub const WATCH_INDEX: &[u8] = b"perun::watched";
+/// Prefix of the local storage keys of submitted refutations.
+pub const SUBMIT_PREFIX: &[u8] = b"perun::submitted::";
+
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug, TypeInfo)]
+/// Latest off-chain state of a watched channel.
+pub struct WatchedState<Params, State, Sig> {
+	pub params: Params,
+	pub state: State,
+	/// Signatures of all participants on `state`.
+	pub sigs: Vec<Sig>,
+}
+
+pub type WatchedStateOf<T> = WatchedState<ParamsOf<T>, StateOf<T>, SigOf<T>>;
+
+/// Submits the transactions of the off-chain watchtower.
+pub trait Watchtower<T: Config> {
+	/// Whether the off-chain worker watches disputes.
+	const ENABLED: bool = true;
+
+	/// Submits `call` and returns whether it was accepted.
+	fn submit(call: Call<T>) -> bool;
+}
+
+/// Disables the watchtower.
+impl<T: Config> Watchtower<T> for () {
+	const ENABLED: bool = false;
+
+	fn submit(_: Call<T>) -> bool {
+		false
+	}
+}
+
+/// Submits the calls as signed transactions of any local account with
+/// the key type of `C`.
+///
+/// The account is paid by [Pallet::dispute] if it is a hired watcher of
+/// the channel.
+pub struct SignedWatchtower<C>(PhantomData<C>);
+
+impl<T, C> Watchtower<T> for SignedWatchtower<C>
+where
+	T: Config + CreateSignedTransaction<Call<T>>,
+	C: AppCrypto<<T as SigningTypes>::Public, <T as SigningTypes>::Signature>,
+{
+	fn submit(call: Call<T>) -> bool {
+		matches!(
+			Signer::<T, C>::any_account().send_signed_transaction(|_| call.clone()),
+			Some((_, Ok(())))
+		)
+	}
+}
+
+/// Submits the calls as unsigned transactions.
+///
+/// Needs no local account. [Pallet::dispute], [Pallet::conclude_final]
+/// and [Pallet::withdraw] are accepted unsigned, so the
+/// [Pallet::progress] of app channels after their timeout fails.
+/// Unsigned refutations do not pay a hired watcher and burn the slashed
+/// part of the refuted [Config::DisputeBond] instead of receiving it.
+pub struct UnsignedWatchtower;
+
+impl<T> Watchtower<T> for UnsignedWatchtower
+where
+	T: Config + SendTransactionTypes<Call<T>>,
+{
+	fn submit(call: Call<T>) -> bool {
+		SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()).is_ok()
+	}
+}
@@ -1,6 +1,11 @@
-//! Autogenerated weights for `pallet_perun`
+//! Weights for `pallet_perun`
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
+//! The weights without a `TODO` were generated with the benchmark CLI below and
+//! the `SequentialVerifier`. The weights marked with `TODO` are estimates and
+//! not benchmarked. Re-run the benchmarks with the `Verifier` of the runtime,
+//! e.g. the `Sr25519BatchVerifier` of the `batch-verify` feature.
+//!
+//! GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
 //! DATE: 2021-10-20, STEPS: `1`, REPEAT: 2, LOW RANGE: `[]`, HIGH RANGE: `[]`
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 128
 
@@ -40,38 +45,158 @@ use crate::{types::{ParamsOf, AppRegistry}, Config};
 /// Weight functions needed for pallet_perun.
 pub trait WeightInfo {
 	fn deposit() -> Weight;
-	fn dispute(p: u32, ) -> Weight;
+	fn deposit_for(p: u32, ) -> Weight;
+	fn dispute(p: u32, e: u32, ) -> Weight;
+	fn dispute_ed25519(p: u32, e: u32, ) -> Weight;
+	fn dispute_ecdsa(p: u32, e: u32, ) -> Weight;
+	fn refute(p: u32, ) -> Weight;
+	fn checkpoint(p: u32, ) -> Weight;
+	fn resume(p: u32, e: u32, ) -> Weight;
 	fn progress<T: Config>(params: &ParamsOf<T>) -> Weight;
 	fn conclude(p: u32, ) -> Weight;
 	fn conclude_final(p: u32, ) -> Weight;
 	fn withdraw() -> Weight;
+	fn approve() -> Weight;
+	fn acknowledge(p: u32, ) -> Weight;
+	fn report_equivocation(p: u32, ) -> Weight;
+	fn register_watcher() -> Weight;
+	fn unregister_watcher() -> Weight;
+	fn hire_watcher(p: u32, ) -> Weight;
+	fn release_hire() -> Weight;
+	fn slash_watcher() -> Weight;
+	fn withdraw_all(w: u32, ) -> Weight;
 }
 
 /// Weights for pallet_perun using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: PerunModule Settled (r:1 w:0)
 	// Storage: PerunModule Deposits (r:1 w:1)
 	// Storage: System Account (r:1 w:1)
 	fn deposit() -> Weight {
 		(Weight::from_all(110_609_000))
-			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	//TODO: benchmark weight and replace estimate
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PerunModule StateRegister (r:1 w:0)
+	// Storage: PerunModule Settled (r:1 w:0)
+	// Storage: PerunModule Deposits (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn deposit_for(p: u32, ) -> Weight {
+		(Weight::from_all(110_609_000))
+			// Encoding and hashing of the participants for the channel id.
+			.saturating_add((Weight::from_all(1_000_000)).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(5))
 			.saturating_add(T::DbWeight::get().writes(2))
 	}
+	//TODO: benchmark weight and replace estimate
 	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PerunModule Checkpoints (r:1 w:0)
 	// Storage: PerunModule StateRegister (r:1 w:1)
-	fn dispute(p: u32, ) -> Weight {
-		(Weight::from_all(1_396_000))
+	// Storage: PerunModule Bonds (r:0 w:1)
+	// Storage: PerunModule Expiries (r:1 w:1)
+	// Storage: PerunModule DisputedParams (r:0 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn dispute(p: u32, e: u32, ) -> Weight {
+		(Weight::from_all(51_396_000))
+			.saturating_add((Weight::from_all(87_897_000)).saturating_mul(p.into()))
+			// Rewrite of the expiries.
+			.saturating_add((Weight::from_all(120_000)).saturating_mul(e.into()))
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(6))
+	}
+	//TODO: benchmark weight and replace estimate
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PerunModule Checkpoints (r:1 w:0)
+	// Storage: PerunModule StateRegister (r:1 w:1)
+	// Storage: PerunModule Bonds (r:0 w:1)
+	// Storage: PerunModule Expiries (r:1 w:1)
+	// Storage: PerunModule DisputedParams (r:0 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn dispute_ed25519(p: u32, e: u32, ) -> Weight {
+		(Weight::from_all(51_396_000))
+			.saturating_add((Weight::from_all(80_000_000)).saturating_mul(p.into()))
+			// Rewrite of the expiries.
+			.saturating_add((Weight::from_all(120_000)).saturating_mul(e.into()))
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(6))
+	}
+	//TODO: benchmark weight and replace estimate
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PerunModule Checkpoints (r:1 w:0)
+	// Storage: PerunModule StateRegister (r:1 w:1)
+	// Storage: PerunModule Bonds (r:0 w:1)
+	// Storage: PerunModule Expiries (r:1 w:1)
+	// Storage: PerunModule DisputedParams (r:0 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn dispute_ecdsa(p: u32, e: u32, ) -> Weight {
+		(Weight::from_all(51_396_000))
+			.saturating_add((Weight::from_all(96_000_000)).saturating_mul(p.into()))
+			// Rewrite of the expiries.
+			.saturating_add((Weight::from_all(120_000)).saturating_mul(e.into()))
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(6))
+	}
+	//TODO: benchmark weight and replace estimate
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PerunModule Checkpoints (r:1 w:0)
+	// Storage: PerunModule StateRegister (r:1 w:1)
+	// Storage: PerunModule Bonds (r:1 w:1)
+	// Storage: PerunModule Hires (r:1 w:1)
+	// Storage: PerunModule Acknowledgements (r:0 w:1)
+	// Storage: System Account (r:3 w:3)
+	fn refute(p: u32, ) -> Weight {
+		(Weight::from_all(151_396_000))
 			// Standard Error: 25_000
+			.saturating_add((Weight::from_all(87_897_000)).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(8))
+			.saturating_add(T::DbWeight::get().writes(7))
+	}
+	//TODO: benchmark weight and replace estimate
+	// Storage: PerunModule StateRegister (r:1 w:0)
+	// Storage: PerunModule Checkpoints (r:1 w:1)
+	fn checkpoint(p: u32, ) -> Weight {
+		(Weight::from_all(1_396_000))
 			.saturating_add((Weight::from_all(87_897_000)).saturating_mul(p.into()))
 			.saturating_add(T::DbWeight::get().reads(2))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
-	//TODO: benchmark weight and replace constant
+	//TODO: benchmark weight and replace estimate
+	// Storage: PerunModule StateRegister (r:1 w:1)
+	// Storage: PerunModule Bonds (r:1 w:1)
+	// Storage: PerunModule Expiries (r:1 w:1)
+	// Storage: PerunModule DisputedParams (r:0 w:1)
+	// Storage: PerunModule Acknowledgements (r:0 w:1)
+	// Storage: PerunModule Checkpoints (r:0 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn resume(p: u32, e: u32, ) -> Weight {
+		(Weight::from_all(51_396_000))
+			.saturating_add((Weight::from_all(87_897_000)).saturating_mul(p.into()))
+			// Rewrite of the expiries.
+			.saturating_add((Weight::from_all(120_000)).saturating_mul(e.into()))
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(8))
+	}
+	//TODO: benchmark weight and replace estimate
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PerunModule StateRegister (r:1 w:1)
+	// Storage: PerunModule Acknowledgements (r:0 w:1)
+	// Storage: PerunModule Expiries (r:1 w:1)
+	// Storage: PerunModule DisputedParams (r:0 w:1)
 	fn progress<U: Config>(params: &ParamsOf<U>) -> Weight {
-		Weight::from_all(10_000).saturating_add(U::AppRegistry::transition_weight(params))
+		// Signature verification of the signer.
+		(Weight::from_all(90_000_000))
+			// Rewrite of the expiries.
+			.saturating_add((Weight::from_all(120_000)).saturating_mul(U::MaxExpiries::get().into()))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(4))
+			.saturating_add(U::AppRegistry::transition_weight(params))
 	}
 	// Storage: PerunModule StateRegister (r:1 w:1)
 	// Storage: PerunModule Deposits (r:2 w:2)
+	// Storage: PerunModule Settled (r:0 w:2)
 	fn conclude(p: u32, ) -> Weight {
 		(Weight::from_all(17_600_000))
 			// Standard Error: 426_000
@@ -79,11 +204,20 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1))
 			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(p.into())))
 			.saturating_add(T::DbWeight::get().writes(1))
-			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(p.into())))
 	}
-	//TODO: benchmark weight and replace constant
-	fn conclude_final(_p: u32, ) -> Weight {
-		Weight::from_all(10_000)
+	//TODO: benchmark weight and replace estimate
+	// Storage: PerunModule StateRegister (r:1 w:1)
+	// Storage: PerunModule Deposits (r:2 w:2)
+	// Storage: PerunModule Settled (r:0 w:2)
+	fn conclude_final(p: u32, ) -> Weight {
+		(Weight::from_all(17_600_000))
+			// Signature verification of dispute and payout of conclude.
+			.saturating_add((Weight::from_all(185_079_000)).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(p.into())))
 	}
 	// Storage: PerunModule StateRegister (r:1 w:0)
 	// Storage: PerunModule Deposits (r:1 w:1)
@@ -93,32 +227,227 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(3))
 			.saturating_add(T::DbWeight::get().writes(2))
 	}
+	//TODO: benchmark weight and replace constant
+	// Storage: PerunModule Approvals (r:0 w:1)
+	fn approve() -> Weight {
+		(Weight::from_all(10_000))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	//TODO: benchmark weight and replace estimate
+	// Storage: PerunModule StateRegister (r:1 w:0)
+	// Storage: PerunModule Acknowledgements (r:1 w:1)
+	fn acknowledge(p: u32, ) -> Weight {
+		(Weight::from_all(96_000_000))
+			// Channel id of the params and the acknowledgements of the others.
+			.saturating_add((Weight::from_all(1_100_000)).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	//TODO: benchmark weight and replace estimate
+	// Storage: PerunModule StateRegister (r:1 w:0)
+	// Storage: PerunModule Equivocators (r:1 w:1)
+	fn report_equivocation(p: u32, ) -> Weight {
+		(Weight::from_all(196_000_000))
+			// Channel id of the params, both state payloads and the other equivocators.
+			.saturating_add((Weight::from_all(1_500_000)).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	//TODO: benchmark weight and replace estimate
+	// Storage: PerunModule Watchers (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn register_watcher() -> Weight {
+		(Weight::from_all(62_000_000))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	//TODO: benchmark weight and replace estimate
+	// Storage: PerunModule Watchers (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn unregister_watcher() -> Weight {
+		(Weight::from_all(60_000_000))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	//TODO: benchmark weight and replace estimate
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PerunModule Watchers (r:1 w:1)
+	// Storage: PerunModule Deposits (r:1 w:0)
+	// Storage: PerunModule StateRegister (r:1 w:0)
+	// Storage: PerunModule Hires (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn hire_watcher(p: u32, ) -> Weight {
+		(Weight::from_all(240_000_000))
+			// Channel id of the params and the funding ids of the participants.
+			.saturating_add((Weight::from_all(2_000_000)).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	//TODO: benchmark weight and replace estimate
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PerunModule Hires (r:1 w:1)
+	// Storage: PerunModule StateRegister (r:1 w:0)
+	// Storage: PerunModule Watchers (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn release_hire() -> Weight {
+		(Weight::from_all(64_000_000))
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	//TODO: benchmark weight and replace estimate
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PerunModule Hires (r:1 w:1)
+	// Storage: PerunModule StateRegister (r:1 w:0)
+	// Storage: PerunModule Watchers (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn slash_watcher() -> Weight {
+		(Weight::from_all(150_000_000))
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	//TODO: benchmark weight and replace estimate
+	// Storage: PerunModule StateRegister (r:1 w:0)
+	// Storage: PerunModule Deposits (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn withdraw_all(w: u32, ) -> Weight {
+		(Weight::from_all(10_000))
+			.saturating_add((Weight::from_all(151_546_000)).saturating_mul(w.into()))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(w.into())))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(w.into())))
+	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
+	// Storage: PerunModule Settled (r:1 w:0)
 	// Storage: PerunModule Deposits (r:1 w:1)
 	// Storage: System Account (r:1 w:1)
 	fn deposit() -> Weight {
 		(Weight::from_all(110_609_000))
-			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().reads(3))
+			.saturating_add(RocksDbWeight::get().writes(2))
+	}
+	//TODO: benchmark weight and replace estimate
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PerunModule StateRegister (r:1 w:0)
+	// Storage: PerunModule Settled (r:1 w:0)
+	// Storage: PerunModule Deposits (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn deposit_for(p: u32, ) -> Weight {
+		(Weight::from_all(110_609_000))
+			// Encoding and hashing of the participants for the channel id.
+			.saturating_add((Weight::from_all(1_000_000)).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(5))
 			.saturating_add(RocksDbWeight::get().writes(2))
 	}
+	//TODO: benchmark weight and replace estimate
 	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PerunModule Checkpoints (r:1 w:0)
 	// Storage: PerunModule StateRegister (r:1 w:1)
-	fn dispute(p: u32, ) -> Weight {
-		(Weight::from_all(1_396_000))
+	// Storage: PerunModule Bonds (r:0 w:1)
+	// Storage: PerunModule Expiries (r:1 w:1)
+	// Storage: PerunModule DisputedParams (r:0 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn dispute(p: u32, e: u32, ) -> Weight {
+		(Weight::from_all(51_396_000))
+			.saturating_add((Weight::from_all(87_897_000)).saturating_mul(p.into()))
+			// Rewrite of the expiries.
+			.saturating_add((Weight::from_all(120_000)).saturating_mul(e.into()))
+			.saturating_add(RocksDbWeight::get().reads(6))
+			.saturating_add(RocksDbWeight::get().writes(6))
+	}
+	//TODO: benchmark weight and replace estimate
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PerunModule Checkpoints (r:1 w:0)
+	// Storage: PerunModule StateRegister (r:1 w:1)
+	// Storage: PerunModule Bonds (r:0 w:1)
+	// Storage: PerunModule Expiries (r:1 w:1)
+	// Storage: PerunModule DisputedParams (r:0 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn dispute_ed25519(p: u32, e: u32, ) -> Weight {
+		(Weight::from_all(51_396_000))
+			.saturating_add((Weight::from_all(80_000_000)).saturating_mul(p.into()))
+			// Rewrite of the expiries.
+			.saturating_add((Weight::from_all(120_000)).saturating_mul(e.into()))
+			.saturating_add(RocksDbWeight::get().reads(6))
+			.saturating_add(RocksDbWeight::get().writes(6))
+	}
+	//TODO: benchmark weight and replace estimate
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PerunModule Checkpoints (r:1 w:0)
+	// Storage: PerunModule StateRegister (r:1 w:1)
+	// Storage: PerunModule Bonds (r:0 w:1)
+	// Storage: PerunModule Expiries (r:1 w:1)
+	// Storage: PerunModule DisputedParams (r:0 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn dispute_ecdsa(p: u32, e: u32, ) -> Weight {
+		(Weight::from_all(51_396_000))
+			.saturating_add((Weight::from_all(96_000_000)).saturating_mul(p.into()))
+			// Rewrite of the expiries.
+			.saturating_add((Weight::from_all(120_000)).saturating_mul(e.into()))
+			.saturating_add(RocksDbWeight::get().reads(6))
+			.saturating_add(RocksDbWeight::get().writes(6))
+	}
+	//TODO: benchmark weight and replace estimate
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PerunModule Checkpoints (r:1 w:0)
+	// Storage: PerunModule StateRegister (r:1 w:1)
+	// Storage: PerunModule Bonds (r:1 w:1)
+	// Storage: PerunModule Hires (r:1 w:1)
+	// Storage: PerunModule Acknowledgements (r:0 w:1)
+	// Storage: System Account (r:3 w:3)
+	fn refute(p: u32, ) -> Weight {
+		(Weight::from_all(151_396_000))
 			// Standard Error: 25_000
+			.saturating_add((Weight::from_all(87_897_000)).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(8))
+			.saturating_add(RocksDbWeight::get().writes(7))
+	}
+	//TODO: benchmark weight and replace estimate
+	// Storage: PerunModule StateRegister (r:1 w:0)
+	// Storage: PerunModule Checkpoints (r:1 w:1)
+	fn checkpoint(p: u32, ) -> Weight {
+		(Weight::from_all(1_396_000))
 			.saturating_add((Weight::from_all(87_897_000)).saturating_mul(p.into()))
 			.saturating_add(RocksDbWeight::get().reads(2))
 			.saturating_add(RocksDbWeight::get().writes(1))
 	}
-	//TODO: benchmark weight and replace constant
+	//TODO: benchmark weight and replace estimate
+	// Storage: PerunModule StateRegister (r:1 w:1)
+	// Storage: PerunModule Bonds (r:1 w:1)
+	// Storage: PerunModule Expiries (r:1 w:1)
+	// Storage: PerunModule DisputedParams (r:0 w:1)
+	// Storage: PerunModule Acknowledgements (r:0 w:1)
+	// Storage: PerunModule Checkpoints (r:0 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn resume(p: u32, e: u32, ) -> Weight {
+		(Weight::from_all(51_396_000))
+			.saturating_add((Weight::from_all(87_897_000)).saturating_mul(p.into()))
+			// Rewrite of the expiries.
+			.saturating_add((Weight::from_all(120_000)).saturating_mul(e.into()))
+			.saturating_add(RocksDbWeight::get().reads(5))
+			.saturating_add(RocksDbWeight::get().writes(8))
+	}
+	//TODO: benchmark weight and replace estimate
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PerunModule StateRegister (r:1 w:1)
+	// Storage: PerunModule Acknowledgements (r:0 w:1)
+	// Storage: PerunModule Expiries (r:1 w:1)
+	// Storage: PerunModule DisputedParams (r:0 w:1)
 	fn progress<U: Config>(params: &ParamsOf<U>) -> Weight {
-		Weight::from_all(10_000).saturating_add(U::AppRegistry::transition_weight(params))
+		// Signature verification of the signer.
+		(Weight::from_all(90_000_000))
+			// Rewrite of the expiries.
+			.saturating_add((Weight::from_all(120_000)).saturating_mul(U::MaxExpiries::get().into()))
+			.saturating_add(RocksDbWeight::get().reads(3))
+			.saturating_add(RocksDbWeight::get().writes(4))
+			.saturating_add(U::AppRegistry::transition_weight(params))
 	}
 	// Storage: PerunModule StateRegister (r:1 w:1)
 	// Storage: PerunModule Deposits (r:2 w:2)
+	// Storage: PerunModule Settled (r:0 w:2)
 	fn conclude(p: u32, ) -> Weight {
 		(Weight::from_all(17_600_000))
 			// Standard Error: 426_000
@@ -126,11 +455,20 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1))
 			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(p.into())))
 			.saturating_add(RocksDbWeight::get().writes(1))
-			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(p.into())))
 	}
-	//TODO: benchmark weight and replace constant
-	fn conclude_final(_p: u32) -> Weight {
-		Weight::from_all(10_000)
+	//TODO: benchmark weight and replace estimate
+	// Storage: PerunModule StateRegister (r:1 w:1)
+	// Storage: PerunModule Deposits (r:2 w:2)
+	// Storage: PerunModule Settled (r:0 w:2)
+	fn conclude_final(p: u32, ) -> Weight {
+		(Weight::from_all(17_600_000))
+			// Signature verification of dispute and payout of conclude.
+			.saturating_add((Weight::from_all(185_079_000)).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().writes(1))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(p.into())))
 	}
 	// Storage: PerunModule StateRegister (r:1 w:0)
 	// Storage: PerunModule Deposits (r:1 w:1)
@@ -140,4 +478,94 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(3))
 			.saturating_add(RocksDbWeight::get().writes(2))
 	}
+	//TODO: benchmark weight and replace constant
+	// Storage: PerunModule Approvals (r:0 w:1)
+	fn approve() -> Weight {
+		(Weight::from_all(10_000))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	//TODO: benchmark weight and replace estimate
+	// Storage: PerunModule StateRegister (r:1 w:0)
+	// Storage: PerunModule Acknowledgements (r:1 w:1)
+	fn acknowledge(p: u32, ) -> Weight {
+		(Weight::from_all(96_000_000))
+			// Channel id of the params and the acknowledgements of the others.
+			.saturating_add((Weight::from_all(1_100_000)).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	//TODO: benchmark weight and replace estimate
+	// Storage: PerunModule StateRegister (r:1 w:0)
+	// Storage: PerunModule Equivocators (r:1 w:1)
+	fn report_equivocation(p: u32, ) -> Weight {
+		(Weight::from_all(196_000_000))
+			// Channel id of the params, both state payloads and the other equivocators.
+			.saturating_add((Weight::from_all(1_500_000)).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	//TODO: benchmark weight and replace estimate
+	// Storage: PerunModule Watchers (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn register_watcher() -> Weight {
+		(Weight::from_all(62_000_000))
+			.saturating_add(RocksDbWeight::get().reads(3))
+			.saturating_add(RocksDbWeight::get().writes(3))
+	}
+	//TODO: benchmark weight and replace estimate
+	// Storage: PerunModule Watchers (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn unregister_watcher() -> Weight {
+		(Weight::from_all(60_000_000))
+			.saturating_add(RocksDbWeight::get().reads(3))
+			.saturating_add(RocksDbWeight::get().writes(3))
+	}
+	//TODO: benchmark weight and replace estimate
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PerunModule Watchers (r:1 w:1)
+	// Storage: PerunModule Deposits (r:1 w:0)
+	// Storage: PerunModule StateRegister (r:1 w:0)
+	// Storage: PerunModule Hires (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn hire_watcher(p: u32, ) -> Weight {
+		(Weight::from_all(240_000_000))
+			// Channel id of the params and the funding ids of the participants.
+			.saturating_add((Weight::from_all(2_000_000)).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(6))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().writes(4))
+	}
+	//TODO: benchmark weight and replace estimate
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PerunModule Hires (r:1 w:1)
+	// Storage: PerunModule StateRegister (r:1 w:0)
+	// Storage: PerunModule Watchers (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn release_hire() -> Weight {
+		(Weight::from_all(64_000_000))
+			.saturating_add(RocksDbWeight::get().reads(6))
+			.saturating_add(RocksDbWeight::get().writes(4))
+	}
+	//TODO: benchmark weight and replace estimate
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PerunModule Hires (r:1 w:1)
+	// Storage: PerunModule StateRegister (r:1 w:0)
+	// Storage: PerunModule Watchers (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn slash_watcher() -> Weight {
+		(Weight::from_all(150_000_000))
+			.saturating_add(RocksDbWeight::get().reads(6))
+			.saturating_add(RocksDbWeight::get().writes(4))
+	}
+	//TODO: benchmark weight and replace estimate
+	// Storage: PerunModule StateRegister (r:1 w:0)
+	// Storage: PerunModule Deposits (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn withdraw_all(w: u32, ) -> Weight {
+		(Weight::from_all(10_000))
+			.saturating_add((Weight::from_all(151_546_000)).saturating_mul(w.into()))
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(w.into())))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(w.into())))
+	}
 }
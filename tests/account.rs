@@ -0,0 +1,138 @@
+//  Copyright 2021 PolyCrypt GmbH
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+mod common;
+use common::mock::*;
+use common::utils::*;
+
+use frame_support::{assert_noop, assert_ok};
+use pallet_perun::types::{HasherOf, SigOf, StateOf, WithdrawalOf};
+use sp_core::{crypto::Pair, Hasher};
+
+/// Makes bob an account participant and returns the state signatures
+/// without a valid signature of bob.
+fn setup_account(setup: &Setup, state: &StateOf<Test>) -> Vec<SigOf<Test>> {
+	PerunAccountParticipant::set(Some((setup.keys.bob.public(), setup.ids.bob)));
+	let mut sigs = sign_state(state, setup);
+	sigs[1] = Default::default();
+	sigs
+}
+
+#[test]
+/// An account participant approves by being the origin.
+fn account_origin() {
+	run_test(MOCK_APP, |setup| {
+		let sigs = setup_account(setup, &setup.state);
+
+		assert_noop!(
+			Perun::dispute(
+				RuntimeOrigin::signed(setup.ids.carl),
+				setup.params.clone(),
+				setup.state.clone(),
+				sigs.clone()
+			),
+			pallet_perun::Error::<Test>::InvalidSignature
+		);
+		assert_ok!(Perun::dispute(
+			RuntimeOrigin::signed(setup.ids.bob),
+			setup.params.clone(),
+			setup.state.clone(),
+			sigs
+		));
+	});
+}
+
+#[test]
+/// An account participant approves with a prior `approve` call.
+fn account_approve() {
+	run_test(MOCK_APP, |setup| {
+		let mut state = setup.state.clone();
+		state.finalized = true;
+		let sigs = setup_account(setup, &state);
+		let hash = HasherOf::<Test>::hash(&Perun::state_payload(&state).unwrap());
+
+		assert_ok!(Perun::approve(RuntimeOrigin::signed(setup.ids.bob), hash));
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::Perun(pallet_perun::Event::Approved(setup.ids.bob, hash))
+		);
+		assert_ok!(Perun::conclude_final(
+			RuntimeOrigin::signed(setup.ids.carl),
+			setup.params.clone(),
+			state,
+			sigs
+		));
+	});
+}
+
+#[test]
+/// Only the approval of the participants account counts.
+fn account_approve_other() {
+	run_test(MOCK_APP, |setup| {
+		let sigs = setup_account(setup, &setup.state);
+		let hash = HasherOf::<Test>::hash(&Perun::state_payload(&setup.state).unwrap());
+
+		assert_ok!(Perun::approve(RuntimeOrigin::signed(setup.ids.carl), hash));
+		assert_noop!(
+			Perun::dispute(
+				RuntimeOrigin::signed(setup.ids.carl),
+				setup.params.clone(),
+				setup.state.clone(),
+				sigs
+			),
+			pallet_perun::Error::<Test>::InvalidSignature
+		);
+	});
+}
+
+#[test]
+/// An account participant withdraws without a signature.
+fn account_withdraw() {
+	run_test(MOCK_APP, |setup| {
+		deposit_both(setup);
+		let mut state = setup.state.clone();
+		state.finalized = true;
+		let sigs = setup_account(setup, &state);
+		assert_ok!(Perun::conclude_final(
+			RuntimeOrigin::signed(setup.ids.bob),
+			setup.params.clone(),
+			state,
+			sigs
+		));
+
+		let withdrawal = WithdrawalOf::<Test> {
+			channel_id: setup.cid,
+			part: setup.keys.bob.public(),
+			receiver: setup.ids.dora,
+			expiry: None,
+			splits: vec![],
+			fee: 0,
+			relayer: None,
+		};
+		assert_noop!(
+			Perun::withdraw(
+				RuntimeOrigin::signed(setup.ids.carl),
+				withdrawal.clone(),
+				Default::default()
+			),
+			pallet_perun::Error::<Test>::InvalidSignature
+		);
+		assert_ok!(Perun::withdraw(
+			RuntimeOrigin::signed(setup.ids.bob),
+			withdrawal,
+			Default::default()
+		));
+		event_withdrawn(setup.fids.bob);
+	});
+}
@@ -0,0 +1,168 @@
+//  Copyright 2021 PolyCrypt GmbH
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+mod common;
+use common::mock::*;
+use common::utils::*;
+
+use frame_support::{assert_noop, assert_ok};
+use pallet_perun::types::{AcknowledgementOf, VersionOf};
+
+/// Lets the participant `signer` acknowledge `version`.
+fn call_acknowledge(setup: &Setup, version: VersionOf<Test>, signer: u32) {
+	let ack = AcknowledgementOf::<Test> {
+		channel_id: setup.cid,
+		version,
+	};
+	let sigs = sign_acknowledgement(&ack, setup);
+	assert_ok!(Perun::acknowledge(
+		RuntimeOrigin::signed(setup.ids.carl),
+		setup.params.clone(),
+		version,
+		sigs[signer as usize].clone(),
+		signer
+	));
+	assert_eq!(
+		last_event(),
+		RuntimeEvent::Perun(pallet_perun::Event::Acknowledged(setup.cid, signer))
+	);
+}
+
+#[test]
+/// A channel can be concluded right away once all participants acknowledged.
+fn acknowledge_conclude_early() {
+	run_test(MOCK_APP, |setup| {
+		deposit_both(setup);
+		let state = call_dispute(setup, false);
+		call_acknowledge(setup, state.version, 0);
+		call_acknowledge(setup, state.version, 1);
+		// Acknowledging twice has no effect.
+		call_acknowledge(setup, state.version, 1);
+
+		assert_ok!(Perun::conclude(
+			RuntimeOrigin::signed(setup.ids.carl),
+			setup.params.clone()
+		));
+		assert_event_concluded(setup.cid);
+		assert_eq!(Perun::acknowledgements(setup.cid), None);
+	});
+}
+
+#[test]
+fn acknowledge_partial() {
+	run_test(MOCK_APP, |setup| {
+		let state = call_dispute(setup, false);
+		call_acknowledge(setup, state.version, 0);
+
+		assert_noop!(
+			Perun::conclude(RuntimeOrigin::signed(setup.ids.carl), setup.params.clone()),
+			pallet_perun::Error::<Test>::ConcludedTooEarly
+		);
+	});
+}
+
+#[test]
+/// A new registered state resets all acknowledgements.
+fn acknowledge_reset() {
+	run_test(MOCK_APP, |setup| {
+		let state = call_dispute(setup, false);
+		call_acknowledge(setup, state.version, 0);
+		call_acknowledge(setup, state.version, 1);
+
+		let mut state = state;
+		state.version += 1;
+		assert_ok!(Perun::dispute(
+			RuntimeOrigin::signed(setup.ids.carl),
+			setup.params.clone(),
+			state.clone(),
+			sign_state(&state, setup)
+		));
+		assert_noop!(
+			Perun::conclude(RuntimeOrigin::signed(setup.ids.carl), setup.params.clone()),
+			pallet_perun::Error::<Test>::ConcludedTooEarly
+		);
+	});
+}
+
+#[test]
+fn acknowledge_outdated() {
+	run_test(MOCK_APP, |setup| {
+		let state = call_dispute(setup, false);
+		let ack = AcknowledgementOf::<Test> {
+			channel_id: setup.cid,
+			version: state.version - 1,
+		};
+		let sigs = sign_acknowledgement(&ack, setup);
+
+		assert_noop!(
+			Perun::acknowledge(
+				RuntimeOrigin::signed(setup.ids.carl),
+				setup.params.clone(),
+				ack.version,
+				sigs[0].clone(),
+				0
+			),
+			pallet_perun::Error::<Test>::AcknowledgementOutdated
+		);
+	});
+}
+
+#[test]
+fn acknowledge_invalid_sig() {
+	run_test(MOCK_APP, |setup| {
+		let state = call_dispute(setup, false);
+		let ack = AcknowledgementOf::<Test> {
+			channel_id: setup.cid,
+			version: state.version,
+		};
+		let sigs = sign_acknowledgement(&ack, setup);
+
+		assert_noop!(
+			Perun::acknowledge(
+				RuntimeOrigin::signed(setup.ids.carl),
+				setup.params.clone(),
+				ack.version,
+				sigs[1].clone(),
+				0
+			),
+			pallet_perun::Error::<Test>::InvalidSignature
+		);
+		assert_noop!(
+			Perun::acknowledge(
+				RuntimeOrigin::signed(setup.ids.carl),
+				setup.params.clone(),
+				ack.version,
+				sigs[1].clone(),
+				2
+			),
+			pallet_perun::Error::<Test>::InvalidParticipantIndex
+		);
+	});
+}
+
+#[test]
+fn acknowledge_unknown_channel() {
+	run_test(MOCK_APP, |setup| {
+		assert_noop!(
+			Perun::acknowledge(
+				RuntimeOrigin::signed(setup.ids.carl),
+				setup.params.clone(),
+				setup.state.version,
+				Default::default(),
+				0
+			),
+			pallet_perun::Error::<Test>::UnknownChannel
+		);
+	});
+}
@@ -0,0 +1,113 @@
+//  Copyright 2021 PolyCrypt GmbH
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+mod common;
+use common::mock::*;
+use common::utils::*;
+
+use frame_support::assert_ok;
+
+const BOND: u64 = 10;
+
+#[test]
+fn bond_posted() {
+	run_test(MOCK_APP, |setup| {
+		PerunDisputeBond::set(BOND);
+		let balance = Balances::free_balance(setup.ids.carl);
+		call_dispute(setup, false);
+
+		assert_eq!(Balances::free_balance(setup.ids.carl), balance - BOND);
+		assert_eq!(Perun::bonds(setup.cid), Some((setup.ids.carl, BOND)));
+	});
+}
+
+#[test]
+fn bond_insufficient_funds() {
+	run_test(MOCK_APP, |setup| {
+		PerunDisputeBond::set(BOND);
+
+		assert!(Perun::dispute(
+			RuntimeOrigin::signed(setup.ids.dora),
+			setup.params.clone(),
+			setup.state.clone(),
+			sign_state(&setup.state, setup)
+		)
+		.is_err());
+	});
+}
+
+#[test]
+/// Refuting a dispute pays half of the bond to the refuter.
+fn bond_slashed() {
+	run_test(MOCK_APP, |setup| {
+		PerunDisputeBond::set(BOND);
+		let carl = Balances::free_balance(setup.ids.carl);
+		let alice = Balances::free_balance(setup.ids.alice);
+		call_dispute(setup, false);
+
+		let mut state = setup.state.clone();
+		state.version += 1;
+		assert_ok!(dispute_as(setup, setup.ids.alice, &state));
+
+		assert_eq!(Balances::free_balance(setup.ids.carl), carl - BOND / 2);
+		assert_eq!(
+			Balances::free_balance(setup.ids.alice),
+			alice - BOND + BOND / 2
+		);
+		assert_eq!(Perun::bonds(setup.cid), Some((setup.ids.alice, BOND)));
+		assert!(System::events().iter().any(|r| r.event
+			== RuntimeEvent::Perun(pallet_perun::Event::BondSlashed(
+				setup.cid,
+				setup.ids.carl,
+				BOND / 2
+			))));
+	});
+}
+
+#[test]
+fn bond_released_on_conclude() {
+	run_test(MOCK_APP, |setup| {
+		PerunDisputeBond::set(BOND);
+		let balance = Balances::free_balance(setup.ids.carl);
+		call_dispute(setup, false);
+		increment_time(2 * setup.params.challenge_duration);
+
+		assert_ok!(Perun::conclude(
+			RuntimeOrigin::signed(setup.ids.alice),
+			setup.params.clone()
+		));
+		assert_eq!(Balances::free_balance(setup.ids.carl), balance);
+		assert_eq!(Perun::bonds(setup.cid), None);
+	});
+}
+
+#[test]
+fn bond_released_on_resume() {
+	run_test(MOCK_APP, |setup| {
+		PerunDisputeBond::set(BOND);
+		let balance = Balances::free_balance(setup.ids.carl);
+		call_dispute(setup, false);
+
+		let mut state = setup.state.clone();
+		state.version += 1;
+		assert_ok!(Perun::resume(
+			RuntimeOrigin::signed(setup.ids.alice),
+			setup.params.clone(),
+			state.clone(),
+			sign_state(&state, setup)
+		));
+		assert_eq!(Balances::free_balance(setup.ids.carl), balance);
+		assert_eq!(Perun::bonds(setup.cid), None);
+	});
+}
@@ -0,0 +1,130 @@
+//  Copyright 2021 PolyCrypt GmbH
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+mod common;
+use common::mock::*;
+use common::utils::*;
+
+use frame_support::{assert_noop, assert_ok};
+use pallet_perun::types::StateOf;
+
+/// Checkpoints `state` with the signatures of alice and bob.
+fn call_checkpoint(setup: &Setup, state: &StateOf<Test>) {
+	assert_ok!(Perun::checkpoint(
+		RuntimeOrigin::signed(setup.ids.carl),
+		setup.params.clone(),
+		state.clone(),
+		sign_state(state, setup)
+	));
+}
+
+#[test]
+fn checkpoint_ok() {
+	run_test(MOCK_APP, |setup| {
+		call_checkpoint(setup, &setup.state);
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::Perun(pallet_perun::Event::Checkpointed(
+				setup.cid,
+				setup.state.version
+			))
+		);
+		assert_eq!(Perun::checkpoints(setup.cid), Some(setup.state.version));
+		// No dispute was started.
+		assert_eq!(Perun::state_registers(setup.cid), None);
+	});
+}
+
+#[test]
+fn checkpoint_final() {
+	run_test(MOCK_APP, |setup| {
+		let mut state = setup.state.clone();
+		state.finalized = true;
+
+		assert_noop!(
+			Perun::checkpoint(
+				RuntimeOrigin::signed(setup.ids.carl),
+				setup.params.clone(),
+				state.clone(),
+				sign_state(&state, setup)
+			),
+			pallet_perun::Error::<Test>::StateFinal
+		);
+	});
+}
+
+#[test]
+fn checkpoint_version_too_low() {
+	run_test(MOCK_APP, |setup| {
+		call_checkpoint(setup, &setup.state);
+
+		assert_noop!(
+			Perun::checkpoint(
+				RuntimeOrigin::signed(setup.ids.carl),
+				setup.params.clone(),
+				setup.state.clone(),
+				sign_state(&setup.state, setup)
+			),
+			pallet_perun::Error::<Test>::CheckpointVersionTooLow
+		);
+		let mut state = setup.state.clone();
+		state.version += 1;
+		call_checkpoint(setup, &state);
+	});
+}
+
+#[test]
+fn checkpoint_disputed() {
+	run_test(MOCK_APP, |setup| {
+		call_dispute(setup, false);
+		let mut state = setup.state.clone();
+		state.version += 1;
+
+		assert_noop!(
+			Perun::checkpoint(
+				RuntimeOrigin::signed(setup.ids.carl),
+				setup.params.clone(),
+				state.clone(),
+				sign_state(&state, setup)
+			),
+			pallet_perun::Error::<Test>::DisputeActive
+		);
+	});
+}
+
+#[test]
+/// A dispute needs at least the version of the checkpoint.
+fn checkpoint_dispute_version() {
+	run_test(MOCK_APP, |setup| {
+		let mut state = setup.state.clone();
+		state.version += 1;
+		call_checkpoint(setup, &state);
+
+		assert_noop!(
+			Perun::dispute(
+				RuntimeOrigin::signed(setup.ids.carl),
+				setup.params.clone(),
+				setup.state.clone(),
+				sign_state(&setup.state, setup)
+			),
+			pallet_perun::Error::<Test>::DisputeVersionTooLow
+		);
+		assert_ok!(Perun::dispute(
+			RuntimeOrigin::signed(setup.ids.carl),
+			setup.params.clone(),
+			state.clone(),
+			sign_state(&state, setup)
+		));
+	});
+}
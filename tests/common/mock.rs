@@ -17,15 +17,24 @@ use pallet_balances;
 use super::utils::increment_time;
 
 use frame_support::{derive_impl, parameter_types, weights::Weight, PalletId};
-use pallet_perun::types::{
-	AppIdOf, AppRegistry, BalanceOf, FundingIdOf, HasherOf, ParamsOf, ParticipantIndex, StateOf,
+use pallet_perun::{
+	encoding::ScaleEncoding,
+	types::{
+		AccountParticipants, AppIdOf, AppRegistry, BalanceOf, FundingIdOf, ParamsOf,
+		ParticipantIndex, SequentialVerifier, SignatureScheme, StateOf,
+	},
+	watchtower::Watchtower,
+};
+use sp_core::{
+	crypto::*,
+	offchain::{testing::TestOffchainExt, OffchainDbExt, OffchainWorkerExt},
+	ConstU64, H256,
 };
-use sp_core::{crypto::*, ConstU64, H256};
 use sp_runtime::{
 	traits::{BlakeTwo256, IdentityLookup},
-	BuildStorage,
+	BuildStorage, Perbill,
 };
-use sp_std::ops::Range;
+use sp_std::{cell::RefCell, ops::Range};
 
 type Block = frame_system::mocking::MockBlock<Test>;
 
@@ -107,19 +116,42 @@ pub const MOCK_APP: u64 = 1;
 parameter_types! {
 	pub const PerunPalletId: PalletId = PalletId(*b"prnstchs");
 	pub const PerunMinDeposit: u64 = 5;
+	pub static PerunDisputeBond: u64 = 0;
+	pub const PerunBondSlash: Perbill = Perbill::from_percent(50);
+	pub const PerunWatcherBond: u64 = 20;
+	pub const PerunWatcherSlashPeriod: u64 = 10;
 	pub const PerunParticipantNum: Range<u32> = 1..256;
 	pub const NoApp: u64 = NO_APP;
+	pub const PerunMaxAppParamsLen: u32 = 64;
+	pub const PerunSignatureScheme: SignatureScheme = SignatureScheme::Ecdsa;
+	pub static PerunDomainSeparation: bool = true;
+	pub static PerunAccountParticipant: Option<(sp_core::ecdsa::Public, u64)> = None;
+	pub const PerunUnsignedPriority: u64 = 1 << 20;
+	pub const PerunUnsignedLongevity: u64 = 16;
+	pub static PerunMaxUnsignedPerBlock: u32 = 8;
+	pub static PerunMaxExpiries: u32 = 16;
+	pub static WatchtowerAccepts: bool = true;
+	pub const PerunUrgentPriority: u64 = 1 << 10;
+	pub const PerunUrgentPeriod: u64 = 3;
 }
 impl pallet_perun::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type PalletId = PerunPalletId;
 	type MinDeposit = PerunMinDeposit;
+	type DisputeBond = PerunDisputeBond;
+	type BondSlash = PerunBondSlash;
+	type WatcherBond = PerunWatcherBond;
+	type WatcherSlashPeriod = PerunWatcherSlashPeriod;
 	type ParticipantNum = PerunParticipantNum;
 	type Currency = Balances;
 	type Version = u32;
 	type Nonce = [u8; 32];
 	type Signature = sp_core::ecdsa::Signature;
 	type PK = sp_core::ecdsa::Public;
+	type MaxThresholdKeys = frame_support::traits::ConstU32<1>;
+	type SignatureScheme = PerunSignatureScheme;
+	type Verifier = SequentialVerifier;
+	type AccountParticipants = MockAccounts;
 	type Hasher = sp_core::KeccakHasher;
 	type HashValue = H256;
 	type Seconds = u64;
@@ -127,6 +159,18 @@ impl pallet_perun::Config for Test {
 	type AppRegistry = MockRegistry;
 	type AppId = u64;
 	type NoApp = NoApp;
+	type MaxAppParamsLen = PerunMaxAppParamsLen;
+	type DomainSeparation = PerunDomainSeparation;
+	type Encoding = ScaleEncoding;
+	type Watchtower = MockWatchtower;
+	type UnsignedPriority = PerunUnsignedPriority;
+	type UnsignedLongevity = PerunUnsignedLongevity;
+	type MaxUnsignedPerBlock = PerunMaxUnsignedPerBlock;
+	type MaxExpiries = PerunMaxExpiries;
+	type UrgentPriority = PerunUrgentPriority;
+	type UrgentPeriod = PerunUrgentPeriod;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
 }
 
 pub struct IDs {
@@ -158,6 +202,32 @@ pub struct Setup {
 
 pub const MOCK_DATA_VALID: [u8; 1] = [1];
 
+thread_local! {
+	/// Calls that were submitted by the watchtower.
+	pub static SUBMITTED: RefCell<Vec<pallet_perun::Call<Test>>> = RefCell::new(vec![]);
+}
+
+/// Records the submitted calls in [SUBMITTED].
+///
+/// Rejects them if [WatchtowerAccepts] is false.
+pub struct MockWatchtower;
+impl Watchtower<Test> for MockWatchtower {
+	fn submit(call: pallet_perun::Call<Test>) -> bool {
+		SUBMITTED.with(|calls| calls.borrow_mut().push(call));
+		WatchtowerAccepts::get()
+	}
+}
+
+/// Treats the key in [PerunAccountParticipant] as account participant.
+pub struct MockAccounts;
+impl AccountParticipants<sp_core::ecdsa::Public, u64> for MockAccounts {
+	fn account(pk: &sp_core::ecdsa::Public) -> Option<u64> {
+		PerunAccountParticipant::get()
+			.filter(|(key, _)| key == pk)
+			.map(|(_, account)| account)
+	}
+}
+
 pub struct MockRegistry {}
 impl AppRegistry<Test> for MockRegistry {
 	fn valid_transition(
@@ -167,7 +237,11 @@ impl AppRegistry<Test> for MockRegistry {
 		_signer: ParticipantIndex,
 	) -> bool {
 		match params.app {
-			MOCK_APP => return to.data == MOCK_DATA_VALID,
+			// The mock app also accepts its app params as valid data.
+			MOCK_APP => {
+				return to.data == MOCK_DATA_VALID
+					|| (!params.app_params.is_empty() && to.data[..] == params.app_params[..])
+			}
 			_ => return false,
 		}
 	}
@@ -180,7 +254,18 @@ impl AppRegistry<Test> for MockRegistry {
 	}
 }
 
+/// Returns the on-chain accounts of the test participants.
+pub fn new_ids() -> IDs {
+	IDs {
+		alice: 1,
+		bob: 2,
+		carl: 3,
+		dora: 4,
+	}
+}
+
 /// Creates a new `Setup` struct.
+/// Must be called within the test externalities.
 pub fn new_setup(app: AppIdOf<Test>) -> Setup {
 	let keys = [
 		sp_core::ecdsa::Pair::from_string("//Alice///password", None).unwrap(),
@@ -195,16 +280,13 @@ pub fn new_setup(app: AppIdOf<Test>) -> Setup {
 		participants: vec![keys[0].public(), keys[1].public()],
 		challenge_duration: 10,
 		app,
+		app_params: Default::default(),
+		payouts: vec![],
 	};
-	let cid = params.channel_id::<HasherOf<Test>>();
+	let cid = params.channel_id::<Test>().unwrap();
 
 	Setup {
-		ids: IDs {
-			alice: 1,
-			bob: 2,
-			carl: 3,
-			dora: 4,
-		},
+		ids: new_ids(),
 		keys: KeyPairs {
 			alice: keys[0].clone(),
 			bob: keys[1].clone(),
@@ -229,23 +311,28 @@ pub fn new_setup(app: AppIdOf<Test>) -> Setup {
 /// This function builds a genesis block and a setup.
 /// The Setup is passed to `test`.
 pub fn run_test(app: AppIdOf<Test>, test: fn(&Setup) -> ()) {
-	let setup = new_setup(app);
+	let ids = new_ids();
 	let mut ext: sp_io::TestExternalities = RuntimeGenesisConfig {
 		// We use default for brevity, but you can configure as desired if needed.
 		system: Default::default(),
 		balances: pallet_balances::GenesisConfig::<Test> {
 			balances: vec![
-				(setup.ids.alice, 100),
-				(setup.ids.bob, 100),
-				(setup.ids.carl, BalanceOf::<Test>::MAX / 2),
-				(setup.ids.dora, 1),
+				(ids.alice, 100),
+				(ids.bob, 100),
+				(ids.carl, BalanceOf::<Test>::MAX / 2),
+				(ids.dora, 1),
 			],
 		},
 	}
 	.build_storage()
 	.unwrap()
 	.into();
+	// Provide the local storage of the off-chain watchtower.
+	let (offchain, _) = TestOffchainExt::new();
+	ext.register_extension(OffchainDbExt::new(offchain.clone()));
+	ext.register_extension(OffchainWorkerExt::new(offchain));
 	// Start at block 1 to enable event emission.
 	ext.execute_with(|| increment_time(1));
-	ext.execute_with(|| test(&setup))
+	// The funding ids depend on the genesis hash.
+	ext.execute_with(|| test(&new_setup(app)))
 }
@@ -16,13 +16,14 @@
 
 use super::mock::*;
 
-use codec::Encode;
 use frame_support::{
 	assert_ok,
+	dispatch::DispatchResultWithPostInfo,
 	traits::{OnFinalize, OnInitialize},
 };
 use pallet_perun::types::{
-	AppIdOf, ChannelIdOf, FundingIdOf, SecondsOf, SigOf, StateOf, VersionOf, WithdrawalOf,
+	AcknowledgementOf, AppIdOf, ChannelIdOf, FundingIdOf, SecondsOf, SigOf, StateOf, VersionOf,
+	WithdrawalOf,
 };
 use sp_core::{crypto::*, H256};
 
@@ -123,17 +124,74 @@ pub fn call_dispute(setup: &Setup, finalized: bool) -> StateOf<Test> {
 	state
 }
 
+/// Returns the state of `setup` with an incremented version.
+pub fn next_state(setup: &Setup) -> StateOf<Test> {
+	let mut state = setup.state.clone();
+	state.version += 1;
+	state
+}
+
+/// Disputes `state` on behalf of `who`.
+pub fn dispute_as(setup: &Setup, who: u64, state: &StateOf<Test>) -> DispatchResultWithPostInfo {
+	Perun::dispute(
+		RuntimeOrigin::signed(who),
+		setup.params.clone(),
+		state.clone(),
+		sign_state(state, setup),
+	)
+}
+
+/// Funds the channel of `setup` and concludes it with its finalized state
+/// on behalf of `origin`.
+pub fn conclude_funded(setup: &Setup, origin: RuntimeOrigin) {
+	deposit_both(setup);
+	let mut state = setup.state.clone();
+	state.finalized = true;
+	assert_ok!(Perun::conclude_final(
+		origin,
+		setup.params.clone(),
+		state.clone(),
+		sign_state(&state, setup)
+	));
+	assert_event_concluded(setup.cid);
+}
+
+/// Returns a withdrawal of `part` to `receiver` without fee.
+pub fn withdrawal(setup: &Setup, part: &sp_core::ecdsa::Pair, receiver: u64) -> WithdrawalOf<Test> {
+	WithdrawalOf::<Test> {
+		channel_id: setup.cid,
+		receiver,
+		part: part.public(),
+		expiry: None,
+		splits: vec![],
+		fee: 0,
+		relayer: None,
+	}
+}
+
 /// Creates off-chain signatures for `state` with alice and bob.
 pub fn sign_state(state: &StateOf<Test>, setup: &Setup) -> Vec<SigOf<Test>> {
-	let raw = Encode::encode(&state);
+	let raw = Perun::state_payload(state).unwrap();
 	let sig_alice = setup.keys.alice.sign(&raw);
 	let sig_bob = setup.keys.bob.sign(&raw);
 	vec![sig_alice, sig_bob]
 }
 
 /// Creates off-chain signatures for `withdrawal` with alice and bob.
+///
+/// Signs the current deposit of the participant as withdrawn amount.
 pub fn sign_withdrawal(withdrawal: &WithdrawalOf<Test>, setup: &Setup) -> Vec<SigOf<Test>> {
-	let raw = Encode::encode(&withdrawal);
+	let fid = Perun::calc_funding_id(withdrawal.channel_id, &withdrawal.part);
+	let amount = Perun::deposits(fid).unwrap_or_default();
+	let raw = Perun::withdrawal_payload(withdrawal, &amount).unwrap();
+	let sig_alice = setup.keys.alice.sign(&raw);
+	let sig_bob = setup.keys.bob.sign(&raw);
+	vec![sig_alice, sig_bob]
+}
+
+/// Creates off-chain signatures for `ack` with alice and bob.
+pub fn sign_acknowledgement(ack: &AcknowledgementOf<Test>, setup: &Setup) -> Vec<SigOf<Test>> {
+	let raw = Perun::acknowledgement_payload(ack).unwrap();
 	let sig_alice = setup.keys.alice.sign(&raw);
 	let sig_bob = setup.keys.bob.sign(&raw);
 	vec![sig_alice, sig_bob]
@@ -151,3 +209,8 @@ pub fn deposit_both(setup: &Setup) {
 		setup.state.balances[1]
 	));
 }
+
+/// Returns and clears the calls that were submitted by the watchtower.
+pub fn take_submitted() -> Vec<pallet_perun::Call<Test>> {
+	SUBMITTED.with(|calls| calls.take())
+}
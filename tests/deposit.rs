@@ -17,7 +17,10 @@ use common::mock::*;
 use common::utils::*;
 
 use frame_support::{assert_noop, assert_ok};
-use pallet_perun::{types::BalanceOf, Error};
+use pallet_perun::{
+	types::{BalanceOf, SecondsOf},
+	Error,
+};
 
 #[test]
 /// tests that depositing funds to a funding id works.
@@ -144,3 +147,108 @@ fn deposit_overflow() {
 		);
 	});
 }
+
+#[test]
+/// Tests that depositing into a concluded channel errors.
+fn deposit_concluded() {
+	run_test(MOCK_APP, |setup| {
+		let mut state = setup.state.clone();
+		state.finalized = true;
+		state.balances = vec![0, 0];
+		let sigs = sign_state(&state, &setup);
+		assert_ok!(Perun::conclude_final(
+			RuntimeOrigin::signed(setup.ids.alice),
+			setup.params.clone(),
+			state,
+			sigs
+		));
+
+		assert_noop!(
+			Perun::deposit(RuntimeOrigin::signed(setup.ids.alice), setup.fids.alice, 10),
+			Error::<Test>::AlreadyConcluded
+		);
+		assert_eq!(Perun::deposits(setup.fids.alice), None);
+	});
+}
+
+#[test]
+/// Tests that `deposit_for` deposits into the funding id of the participant.
+fn deposit_for_ok() {
+	run_test(MOCK_APP, |setup| {
+		assert_ok!(Perun::deposit_for(
+			RuntimeOrigin::signed(setup.ids.alice),
+			setup.params.clone(),
+			1,
+			10
+		));
+		assert_event_deposited(setup.fids.bob, 10);
+		assert_eq!(Perun::deposits(setup.fids.bob), Some(10));
+		assert_eq!(Balances::free_balance(setup.ids.alice), 90);
+	});
+}
+
+#[test]
+fn deposit_for_invalid_participant_index() {
+	run_test(MOCK_APP, |setup| {
+		assert_noop!(
+			Perun::deposit_for(
+				RuntimeOrigin::signed(setup.ids.alice),
+				setup.params.clone(),
+				2,
+				10
+			),
+			Error::<Test>::InvalidParticipantIndex
+		);
+		assert_no_events();
+	});
+}
+
+#[test]
+fn deposit_for_invalid_params() {
+	run_test(MOCK_APP, |setup| {
+		let mut params = setup.params.clone();
+		params.participants = vec![];
+		assert_noop!(
+			Perun::deposit_for(RuntimeOrigin::signed(setup.ids.alice), params, 0, 10),
+			Error::<Test>::InvalidParticipantNum
+		);
+
+		let mut params = setup.params.clone();
+		params.challenge_duration = 0;
+		assert_noop!(
+			Perun::deposit_for(RuntimeOrigin::signed(setup.ids.alice), params, 0, 10),
+			Error::<Test>::InvalidChallengeDuration
+		);
+
+		let mut params = setup.params.clone();
+		params.challenge_duration = SecondsOf::<Test>::MAX;
+		assert_noop!(
+			Perun::deposit_for(RuntimeOrigin::signed(setup.ids.alice), params, 0, 10),
+			Error::<Test>::ChallengeDurationOverflow
+		);
+		assert_no_events();
+	});
+}
+
+#[test]
+fn deposit_for_concluded() {
+	run_test(MOCK_APP, |setup| {
+		deposit_both(&setup);
+		call_dispute(&setup, false);
+		increment_time(2 * setup.params.challenge_duration);
+		assert_ok!(Perun::conclude(
+			RuntimeOrigin::signed(setup.ids.alice),
+			setup.params.clone(),
+		));
+
+		assert_noop!(
+			Perun::deposit_for(
+				RuntimeOrigin::signed(setup.ids.alice),
+				setup.params.clone(),
+				0,
+				10
+			),
+			Error::<Test>::AlreadyConcluded
+		);
+	});
+}
@@ -16,8 +16,9 @@ mod common;
 use common::mock::*;
 use common::utils::*;
 
+use codec::{Decode, Encode};
 use frame_support::{assert_noop, assert_ok};
-use pallet_perun::types::{ChannelIdOf, HasherOf, NonceOf, SecondsOf};
+use pallet_perun::types::{ChannelIdOf, NonceOf, Params, ParamsOf, SecondsOf};
 
 #[test]
 fn dispute_ok() {
@@ -30,7 +31,7 @@ fn dispute_ok() {
 			setup.state.clone(),
 			sigs
 		));
-		let channel_id = setup.params.channel_id::<HasherOf<Test>>();
+		let channel_id = setup.params.channel_id::<Test>().unwrap();
 		assert_event_disputed(channel_id, setup.state.clone());
 	});
 }
@@ -90,7 +91,7 @@ fn dispute_challenge_duration_overflow() {
 		let mut params = setup.params.clone();
 		let mut state = setup.state.clone();
 		params.challenge_duration = SecondsOf::<Test>::MAX;
-		state.channel_id = params.channel_id::<HasherOf<Test>>();
+		state.channel_id = params.channel_id::<Test>().unwrap();
 		let sigs = sign_state(&state, &setup);
 
 		increment_time(1);
@@ -227,6 +228,23 @@ fn dispute_invalid_channel_id() {
 		);
 		assert_no_events();
 	});
+	run_test(MOCK_APP, |setup| {
+		let sigs = sign_state(&setup.state, &setup);
+		let mut params = setup.params.clone();
+		params.app_params = vec![1, 2, 3].try_into().unwrap();
+
+		// Different app params
+		assert_noop!(
+			Perun::dispute(
+				RuntimeOrigin::signed(setup.ids.carl),
+				params,
+				setup.state.clone(),
+				sigs,
+			),
+			pallet_perun::Error::<Test>::InvalidChannelId
+		);
+		assert_no_events();
+	});
 	run_test(MOCK_APP, |setup| {
 		let sigs = sign_state(&setup.state, &setup);
 		let mut state = setup.state.clone();
@@ -246,6 +264,23 @@ fn dispute_invalid_channel_id() {
 	});
 }
 
+#[test]
+/// Params with too long app params cannot be decoded.
+fn dispute_app_params_too_long() {
+	run_test(MOCK_APP, |setup| {
+		let params = Params {
+			nonce: setup.params.nonce,
+			participants: setup.params.participants.clone(),
+			challenge_duration: setup.params.challenge_duration,
+			app: setup.params.app,
+			app_params: vec![0u8; PerunMaxAppParamsLen::get() as usize + 1],
+			payouts: setup.params.payouts.clone(),
+		};
+
+		assert!(ParamsOf::<Test>::decode(&mut &params.encode()[..]).is_err());
+	});
+}
+
 #[test]
 fn dispute_same_version() {
 	run_test(MOCK_APP, |setup| {
@@ -0,0 +1,271 @@
+//  Copyright 2021 PolyCrypt GmbH
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+// The expected hashes are printed by the generator in `tests/vectors`, which
+// encodes the same values with the go-perun Ethereum backend.
+
+use frame_support::parameter_types;
+use pallet_perun::{
+	encoding::{AbiEncoding, ValueTooLarge},
+	eth::EthAddress,
+	types::{Acknowledgement, HireOffer, Params, State, WatchReceipt, Withdrawal},
+};
+use sp_core::{keccak_256, H256};
+
+parameter_types! {
+	pub const Asset: EthAddress = EthAddress([0x33; 20]);
+}
+
+type Abi = AbiEncoding<Asset>;
+
+fn alice() -> EthAddress {
+	EthAddress(
+		from_hex("2c7536e3605d9c16a7a3d7b1898e529396a65c23")
+			.try_into()
+			.unwrap(),
+	)
+}
+
+fn bob() -> EthAddress {
+	EthAddress([0x11; 20])
+}
+
+fn new_params(
+	app_params: Vec<u8>,
+) -> Params<[u8; 32], EthAddress, EthAddress, u64, EthAddress, Vec<u8>> {
+	Params {
+		nonce: [1, 2, 3, 4].repeat(8).try_into().unwrap(),
+		participants: vec![alice(), bob()],
+		challenge_duration: 60,
+		app: EthAddress([0x22; 20]),
+		app_params,
+		payouts: vec![],
+	}
+}
+
+fn new_state(data: Vec<u8>, finalized: bool) -> State<H256, u64, u64> {
+	State {
+		channel_id: H256::repeat_byte(1),
+		version: 3,
+		balances: vec![10, 20],
+		finalized,
+		data,
+	}
+}
+
+#[test]
+fn abi_params() {
+	let encoded = Abi::params(&new_params(vec![])).unwrap();
+	assert_eq!(encoded.len(), 320);
+	assert_eq!(
+		keccak_256(&encoded).to_vec(),
+		from_hex("6ebadb34943528dd74b8f9024b0990dec5c65d9ca8262c0bd3d72ff01912f3e5")
+	);
+
+	let encoded = Abi::params(&new_params(vec![5, 6, 7])).unwrap();
+	assert_eq!(encoded.len(), 416);
+	assert_eq!(
+		keccak_256(&encoded).to_vec(),
+		from_hex("69ef905271aad98aa6d99a15e43b471296f9c6df2c30a567c05e4feeffeb34b4")
+	);
+
+	let mut params = new_params(vec![]);
+	params.payouts = vec![bob(), alice()];
+	let encoded = Abi::params(&params).unwrap();
+	assert_eq!(encoded.len(), 512);
+	assert_eq!(
+		keccak_256(&encoded).to_vec(),
+		from_hex("8822cae7b45a0d63b02a33aecd8ac757f7114b145b2535c3e8bcd51d32a17ba8")
+	);
+}
+
+#[test]
+fn abi_state() {
+	let encoded = Abi::state(&new_state(vec![], true)).unwrap();
+	assert_eq!(encoded.len(), 576);
+	assert_eq!(
+		keccak_256(&encoded).to_vec(),
+		from_hex("89ce1533fb14db7d2587fb9e5407b8714c6543114b0fbe154b4f666c5bccb92d")
+	);
+
+	// App data is padded to a multiple of 32 bytes.
+	let encoded = Abi::state(&new_state(vec![0xab; 33], false)).unwrap();
+	assert_eq!(encoded.len(), 640);
+	assert_eq!(
+		keccak_256(&encoded).to_vec(),
+		from_hex("77f6d6efea52e1c3d7a3d6eaaf43005f9f8c2088223e728eaf8dfbd7e1ece335")
+	);
+}
+
+#[test]
+fn abi_withdrawal() {
+	let withdrawal = Withdrawal {
+		channel_id: H256::repeat_byte(1),
+		part: alice(),
+		receiver: bob(),
+		expiry: None::<u64>,
+		splits: vec![],
+		fee: 0,
+		relayer: None,
+	};
+	let encoded = Abi::withdrawal(&withdrawal, &10u64).unwrap();
+	assert_eq!(encoded.len(), 128);
+	assert_eq!(
+		keccak_256(&encoded).to_vec(),
+		from_hex("5fe09026e96057d6ac5543da39e2ca2041df509907f4ec4eebb3ed7bac786669")
+	);
+
+	let withdrawal = Withdrawal {
+		expiry: Some(1000u64),
+		..withdrawal
+	};
+	let encoded = Abi::withdrawal(&withdrawal, &10).unwrap();
+	assert_eq!(encoded.len(), 384);
+	assert_eq!(
+		keccak_256(&encoded).to_vec(),
+		from_hex("14cebd2bf8e5a20ad8c5ae7de0c845fab4bad76108f686738d3465cea1a97f6d")
+	);
+
+	let withdrawal = Withdrawal {
+		splits: vec![(alice(), 7u64)],
+		..withdrawal
+	};
+	let encoded = Abi::withdrawal(&withdrawal, &10).unwrap();
+	assert_eq!(encoded.len(), 448);
+	assert_eq!(
+		keccak_256(&encoded).to_vec(),
+		from_hex("571f2980130c689a87948ec64797fc7c699e196f84c7d262897393283cd57b02")
+	);
+
+	let withdrawal = Withdrawal {
+		splits: vec![],
+		fee: 3,
+		relayer: Some(bob()),
+		..withdrawal
+	};
+	let encoded = Abi::withdrawal(&withdrawal, &10).unwrap();
+	assert_eq!(encoded.len(), 384);
+	assert_eq!(
+		keccak_256(&encoded).to_vec(),
+		from_hex("ab7922d8058e77e6c2ce388fc796a172f1186c143588758c8421bbde8ccb11c8")
+	);
+}
+
+#[test]
+fn abi_funding() {
+	let encoded = Abi::funding(&H256::repeat_byte(1), &alice());
+	assert_eq!(
+		encoded,
+		from_hex(
+			"0101010101010101010101010101010101010101010101010101010101010101\
+			 0000000000000000000000002c7536e3605d9c16a7a3d7b1898e529396a65c23"
+		)
+	);
+}
+
+#[test]
+fn abi_acknowledgement() {
+	let ack = Acknowledgement {
+		channel_id: H256::repeat_byte(1),
+		version: 3u64,
+	};
+	assert_eq!(
+		Abi::acknowledgement(&ack).unwrap(),
+		from_hex(
+			"0101010101010101010101010101010101010101010101010101010101010101\
+			 0000000000000000000000000000000000000000000000000000000000000003"
+		)
+	);
+}
+
+#[test]
+fn abi_receipt() {
+	let receipt = WatchReceipt {
+		channel_id: H256::repeat_byte(2),
+		version: 7u64,
+	};
+	assert_eq!(
+		Abi::receipt(&receipt).unwrap(),
+		from_hex(
+			"0202020202020202020202020202020202020202020202020202020202020202\
+			 0000000000000000000000000000000000000000000000000000000000000007"
+		)
+	);
+}
+
+#[test]
+fn abi_hire_offer() {
+	let offer = HireOffer {
+		channel_id: H256::repeat_byte(2),
+		registrant: alice(),
+		watcher: bob(),
+		fee: 5u64,
+		until: 1000u64,
+	};
+	let encoded = Abi::hire_offer(&offer).unwrap();
+	assert_eq!(encoded.len(), 160);
+	assert_eq!(
+		keccak_256(&encoded).to_vec(),
+		from_hex("4d83ee1e87fe4a06f155e3ee846d64287e067d8b1a6d1384f525f5aec3f3f6ad")
+	);
+}
+
+#[test]
+/// Values that are longer than a word are hashed instead of truncated.
+fn abi_long_value() {
+	let mut part = [0x44u8; 33];
+	part[0] = 2;
+	let encoded = Abi::funding(&H256::repeat_byte(1), &part);
+	assert_eq!(
+		encoded[32..].to_vec(),
+		from_hex("cc16b8bcdfa277d5bbee200edd73cae56eb7238ff7bc6b0a47a11e100a9e3adb")
+	);
+
+	part[0] = 3;
+	assert_ne!(Abi::funding(&H256::repeat_byte(1), &part), encoded);
+}
+
+#[test]
+/// Versions that do not fit into `uint64` are rejected instead of clamped.
+fn abi_value_too_large() {
+	let state = State {
+		channel_id: H256::repeat_byte(1),
+		version: u64::MAX as u128 + 1,
+		balances: vec![10u64, 20],
+		finalized: false,
+		data: vec![],
+	};
+	assert_eq!(Abi::state(&state), Err(ValueTooLarge));
+
+	let ack = Acknowledgement {
+		channel_id: H256::repeat_byte(1),
+		version: u64::MAX as u128 + 1,
+	};
+	assert_eq!(Abi::acknowledgement(&ack), Err(ValueTooLarge));
+
+	let ack = Acknowledgement {
+		version: u64::MAX as u128,
+		..ack
+	};
+	assert!(Abi::acknowledgement(&ack).is_ok());
+}
+
+/// Decodes a hex string without `0x` prefix.
+fn from_hex(hex: &str) -> Vec<u8> {
+	let hex: String = hex.split_whitespace().collect();
+	(0..hex.len())
+		.step_by(2)
+		.map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+		.collect()
+}
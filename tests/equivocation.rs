@@ -0,0 +1,150 @@
+//  Copyright 2021 PolyCrypt GmbH
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+mod common;
+use common::mock::*;
+use common::utils::*;
+
+use frame_support::{assert_noop, assert_ok};
+use pallet_perun::types::StateOf;
+
+/// Returns a state that conflicts with the state of `setup`.
+fn conflicting_state(setup: &Setup) -> StateOf<Test> {
+	let mut state = setup.state.clone();
+	state.balances = vec![5, 10];
+	state
+}
+
+/// Reports that alice signed the state of `setup` and `other`.
+fn report(setup: &Setup, other: &StateOf<Test>) -> frame_support::dispatch::DispatchResult {
+	Perun::report_equivocation(
+		RuntimeOrigin::signed(setup.ids.carl),
+		setup.params.clone(),
+		setup.state.clone(),
+		sign_state(&setup.state, setup)[0].clone(),
+		other.clone(),
+		sign_state(other, setup)[0].clone(),
+		0,
+	)
+}
+
+#[test]
+fn equivocation_ok() {
+	run_test(MOCK_APP, |setup| {
+		assert_ok!(report(setup, &conflicting_state(setup)));
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::Perun(pallet_perun::Event::Equivocated(setup.cid, 0))
+		);
+		assert_eq!(Perun::equivocators(setup.cid), Some(vec![0]));
+
+		assert_noop!(
+			report(setup, &conflicting_state(setup)),
+			pallet_perun::Error::<Test>::EquivocationReported
+		);
+	});
+}
+
+#[test]
+fn equivocation_none() {
+	run_test(MOCK_APP, |setup| {
+		assert_noop!(
+			report(setup, &setup.state),
+			pallet_perun::Error::<Test>::NoEquivocation
+		);
+		let mut state = conflicting_state(setup);
+		state.version += 1;
+		assert_noop!(
+			report(setup, &state),
+			pallet_perun::Error::<Test>::NoEquivocation
+		);
+	});
+}
+
+#[test]
+fn equivocation_invalid_sig() {
+	run_test(MOCK_APP, |setup| {
+		let other = conflicting_state(setup);
+
+		// Signed by bob, but reported for alice.
+		assert_noop!(
+			Perun::report_equivocation(
+				RuntimeOrigin::signed(setup.ids.carl),
+				setup.params.clone(),
+				setup.state.clone(),
+				sign_state(&setup.state, setup)[0].clone(),
+				other.clone(),
+				sign_state(&other, setup)[1].clone(),
+				0,
+			),
+			pallet_perun::Error::<Test>::InvalidSignature
+		);
+	});
+}
+
+#[test]
+/// The outcome of an equivocator goes to the other participants.
+fn equivocation_forfeit() {
+	run_test(MOCK_APP, |setup| {
+		deposit_both(setup);
+		assert_ok!(report(setup, &conflicting_state(setup)));
+		call_dispute(setup, false);
+		increment_time(2 * setup.params.challenge_duration);
+
+		assert_ok!(Perun::conclude(
+			RuntimeOrigin::signed(setup.ids.carl),
+			setup.params.clone()
+		));
+		assert_eq!(Perun::deposits(setup.fids.alice), Some(0));
+		assert_eq!(Perun::deposits(setup.fids.bob), Some(15));
+		assert_eq!(Perun::equivocators(setup.cid), None);
+	});
+}
+
+#[test]
+/// Reports are removed when the channel is concluded without dispute.
+fn equivocation_conclude_final() {
+	run_test(MOCK_APP, |setup| {
+		deposit_both(setup);
+		assert_ok!(report(setup, &conflicting_state(setup)));
+		let mut state = setup.state.clone();
+		state.finalized = true;
+
+		assert_ok!(Perun::conclude_final(
+			RuntimeOrigin::signed(setup.ids.carl),
+			setup.params.clone(),
+			state.clone(),
+			sign_state(&state, setup)
+		));
+		assert_eq!(Perun::deposits(setup.fids.alice), Some(0));
+		assert_eq!(Perun::equivocators(setup.cid), None);
+	});
+}
+
+#[test]
+fn equivocation_concluded() {
+	run_test(MOCK_APP, |setup| {
+		call_dispute(setup, false);
+		increment_time(2 * setup.params.challenge_duration);
+		assert_ok!(Perun::conclude(
+			RuntimeOrigin::signed(setup.ids.carl),
+			setup.params.clone()
+		));
+
+		assert_noop!(
+			report(setup, &conflicting_state(setup)),
+			pallet_perun::Error::<Test>::AlreadyConcluded
+		);
+	});
+}
@@ -0,0 +1,175 @@
+//  Copyright 2021 PolyCrypt GmbH
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+mod common;
+use common::mock::*;
+use common::utils::*;
+
+use frame_support::{
+	assert_ok,
+	traits::{Get, Hooks},
+	weights::Weight,
+};
+use pallet_perun::{types::Phase, weights::WeightInfo};
+
+/// Runs `on_idle` with `weight` in the current block.
+fn idle(weight: Weight) -> Weight {
+	Perun::on_idle(System::block_number(), weight)
+}
+
+#[test]
+fn expiry_conclude_on_idle() {
+	run_test(NO_APP, |setup| {
+		deposit_both(setup);
+		call_dispute(setup, false);
+		let timeout = Perun::state_registers(setup.cid).unwrap().timeout;
+		assert_eq!(Perun::expiries(), vec![(timeout, setup.cid)]);
+		assert_eq!(
+			Perun::disputed_params(setup.cid),
+			Some(setup.params.clone())
+		);
+
+		// Not expired yet.
+		idle(Weight::MAX);
+		assert_eq!(Perun::expiries().len(), 1);
+
+		increment_time(setup.params.challenge_duration);
+		idle(Weight::MAX);
+		assert_event_concluded(setup.cid);
+		assert_eq!(Perun::expiries(), vec![]);
+		assert_eq!(Perun::disputed_params(setup.cid), None);
+		assert_eq!(Perun::deposits(setup.fids.alice), Some(10));
+		assert_eq!(Perun::deposits(setup.fids.bob), Some(5));
+	});
+}
+
+#[test]
+/// Nothing is concluded without enough weight.
+fn expiry_weight_limit() {
+	run_test(NO_APP, |setup| {
+		call_dispute(setup, false);
+		increment_time(setup.params.challenge_duration);
+
+		assert_eq!(idle(Weight::zero()), Weight::zero());
+		assert_eq!(Perun::expiries().len(), 1);
+	});
+}
+
+#[test]
+/// App channels expire after the progression period.
+fn expiry_app_channel() {
+	run_test(MOCK_APP, |setup| {
+		call_dispute(setup, false);
+		let timeout = Perun::state_registers(setup.cid).unwrap().timeout;
+		let duration = setup.params.challenge_duration;
+		assert_eq!(Perun::expiries(), vec![(timeout + duration, setup.cid)]);
+
+		increment_time(duration);
+		let mut state = setup.state.clone();
+		state.version += 1;
+		state.data = MOCK_DATA_VALID.to_vec();
+		assert_ok!(Perun::progress(
+			RuntimeOrigin::signed(setup.ids.carl),
+			setup.params.clone(),
+			state.clone(),
+			sign_state(&state, setup)[0].clone(),
+			0
+		));
+		assert_eq!(Perun::expiries(), vec![(timeout + duration, setup.cid)]);
+
+		increment_time(duration);
+		idle(Weight::MAX);
+		assert_event_concluded(setup.cid);
+	});
+}
+
+#[test]
+/// Concluded and resumed channels are removed from the index.
+fn expiry_removed() {
+	run_test(NO_APP, |setup| {
+		call_dispute(setup, false);
+		increment_time(setup.params.challenge_duration);
+		assert_ok!(Perun::conclude(
+			RuntimeOrigin::signed(setup.ids.carl),
+			setup.params.clone()
+		));
+		assert_eq!(Perun::expiries(), vec![]);
+	});
+
+	run_test(NO_APP, |setup| {
+		call_dispute(setup, false);
+		let mut state = setup.state.clone();
+		state.version += 1;
+		assert_ok!(Perun::resume(
+			RuntimeOrigin::signed(setup.ids.carl),
+			setup.params.clone(),
+			state.clone(),
+			sign_state(&state, setup)
+		));
+		assert_eq!(Perun::expiries(), vec![]);
+		assert_eq!(Perun::disputed_params(setup.cid), None);
+	});
+}
+
+#[test]
+/// Disputes beyond the maximum are not scheduled but can be concluded.
+fn expiry_full() {
+	run_test(NO_APP, |setup| {
+		PerunMaxExpiries::set(0);
+		deposit_both(setup);
+		call_dispute(setup, false);
+		assert_eq!(Perun::expiries(), vec![]);
+		assert_eq!(Perun::disputed_params(setup.cid), None);
+
+		increment_time(setup.params.challenge_duration);
+		idle(Weight::MAX);
+		assert_eq!(
+			Perun::state_registers(setup.cid).unwrap().phase,
+			Phase::Register
+		);
+		assert_ok!(Perun::conclude(
+			RuntimeOrigin::signed(setup.ids.carl),
+			setup.params.clone()
+		));
+		assert_event_concluded(setup.cid);
+	});
+}
+
+#[test]
+/// Each conclusion in `on_idle` is charged with its payouts and settlement.
+fn expiry_weight_per_item() {
+	run_test(NO_APP, |setup| {
+		call_dispute(setup, false);
+		increment_time(setup.params.challenge_duration);
+		let db = <Test as frame_system::Config>::DbWeight::get();
+		let read = db.reads_writes(3, 2);
+		let conclude = read
+			.saturating_add(<() as WeightInfo>::conclude(2))
+			.saturating_add(
+				db.reads_writes(2, 2)
+					.saturating_mul(setup.params.payouts.len() as u64),
+			)
+			.saturating_add(db.reads_writes(6, 7));
+
+		// Not enough to conclude.
+		assert_eq!(
+			idle(conclude.saturating_sub(Weight::from_parts(1, 0))),
+			read
+		);
+		assert_eq!(Perun::expiries().len(), 1);
+
+		assert_eq!(idle(conclude), conclude);
+		assert_eq!(Perun::expiries(), vec![]);
+	});
+}
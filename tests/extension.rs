@@ -0,0 +1,234 @@
+//  Copyright 2021 PolyCrypt GmbH
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+mod common;
+use common::mock::*;
+use common::utils::*;
+
+use frame_support::{assert_noop, assert_ok, dispatch::DispatchInfo};
+use pallet_perun::{extension::CheckPerunCall, types::StateOf};
+use sp_runtime::{
+	traits::SignedExtension,
+	transaction_validity::{InvalidTransaction, TransactionPriority, TransactionValidityError},
+	DispatchError,
+};
+
+/// Validates `call` as signed transaction of `who` and returns its
+/// priority.
+fn validate(
+	who: u64,
+	call: pallet_perun::Call<Test>,
+) -> Result<TransactionPriority, TransactionValidityError> {
+	CheckPerunCall::<Test>::new()
+		.validate(&who, &RuntimeCall::Perun(call), &DispatchInfo::default(), 0)
+		.map(|valid| valid.priority)
+}
+
+/// Returns the invalid transaction error that `err` is mapped to.
+fn invalid(err: pallet_perun::Error<Test>) -> TransactionValidityError {
+	match DispatchError::from(err) {
+		DispatchError::Module(err) => InvalidTransaction::Custom(err.error[0]).into(),
+		_ => unreachable!(),
+	}
+}
+
+fn dispute_call(setup: &Setup, state: &StateOf<Test>) -> pallet_perun::Call<Test> {
+	pallet_perun::Call::dispute {
+		params: setup.params.clone(),
+		state: state.clone(),
+		state_sigs: sign_state(state, setup),
+	}
+}
+
+#[test]
+/// Signatures are only checked on dispatch.
+fn extension_dispute_invalid_sig() {
+	run_test(MOCK_APP, |setup| {
+		let mut sigs = sign_state(&setup.state, setup);
+		sigs[0] = sigs[1].clone();
+		let call = pallet_perun::Call::dispute {
+			params: setup.params.clone(),
+			state: setup.state.clone(),
+			state_sigs: sigs.clone(),
+		};
+
+		assert_eq!(validate(setup.ids.carl, call.clone()), Ok(0));
+		assert_eq!(
+			CheckPerunCall::<Test>::new().pre_dispatch(
+				&setup.ids.carl,
+				&RuntimeCall::Perun(call),
+				&DispatchInfo::default(),
+				0
+			),
+			Ok(())
+		);
+		assert_noop!(
+			Perun::dispute(
+				RuntimeOrigin::signed(setup.ids.carl),
+				setup.params.clone(),
+				setup.state.clone(),
+				sigs
+			),
+			pallet_perun::Error::<Test>::InvalidSignature
+		);
+	});
+}
+
+#[test]
+fn extension_dispute_invalid_channel() {
+	run_test(MOCK_APP, |setup| {
+		let mut state = setup.state.clone();
+		state.channel_id = Default::default();
+
+		assert_eq!(
+			validate(setup.ids.carl, dispute_call(setup, &state)),
+			Err(invalid(pallet_perun::Error::<Test>::InvalidChannelId))
+		);
+	});
+}
+
+#[test]
+fn extension_dispute_version_too_low() {
+	run_test(MOCK_APP, |setup| {
+		assert_eq!(
+			validate(setup.ids.carl, dispute_call(setup, &setup.state)),
+			Ok(0)
+		);
+		call_dispute(setup, false);
+
+		assert_eq!(
+			validate(setup.ids.alice, dispute_call(setup, &setup.state)),
+			Err(invalid(pallet_perun::Error::<Test>::DisputeVersionTooLow))
+		);
+	});
+}
+
+#[test]
+/// Refutations get a priority boost shortly before the timeout.
+fn extension_dispute_urgent() {
+	run_test(MOCK_APP, |setup| {
+		call_dispute(setup, false);
+		let call = dispute_call(setup, &next_state(setup));
+		assert_eq!(validate(setup.ids.alice, call.clone()), Ok(0));
+
+		increment_time(setup.params.challenge_duration - PerunUrgentPeriod::get());
+		assert_eq!(
+			validate(setup.ids.alice, call.clone()),
+			Ok(PerunUrgentPriority::get())
+		);
+
+		increment_time(PerunUrgentPeriod::get() + 1);
+		assert_eq!(
+			validate(setup.ids.alice, call),
+			Err(invalid(pallet_perun::Error::<Test>::DisputeTimedOut))
+		);
+	});
+}
+
+#[test]
+fn extension_progress() {
+	run_test(MOCK_APP, |setup| {
+		call_dispute(setup, false);
+		let mut state = next_state(setup);
+		state.data = MOCK_DATA_VALID.to_vec();
+		let sigs = sign_state(&state, setup);
+		let call = pallet_perun::Call::progress {
+			params: setup.params.clone(),
+			next: state,
+			sig: sigs[0].clone(),
+			signer: 0,
+		};
+
+		assert_eq!(
+			validate(setup.ids.alice, call.clone()),
+			Err(invalid(pallet_perun::Error::<Test>::TooEarly))
+		);
+		increment_time(setup.params.challenge_duration);
+		assert_eq!(validate(setup.ids.alice, call), Ok(0));
+	});
+}
+
+#[test]
+/// The signer of a progression must be a participant.
+fn extension_progress_invalid_signer() {
+	run_test(MOCK_APP, |setup| {
+		call_dispute(setup, false);
+		increment_time(setup.params.challenge_duration);
+		let mut state = next_state(setup);
+		state.data = MOCK_DATA_VALID.to_vec();
+		let sig = sign_state(&state, setup)[0].clone();
+		let signer = setup.params.participants.len() as u32;
+		let call = pallet_perun::Call::progress {
+			params: setup.params.clone(),
+			next: state.clone(),
+			sig: sig.clone(),
+			signer,
+		};
+
+		assert_eq!(
+			validate(setup.ids.alice, call),
+			Err(invalid(
+				pallet_perun::Error::<Test>::InvalidParticipantIndex
+			))
+		);
+		assert_noop!(
+			Perun::progress(
+				RuntimeOrigin::signed(setup.ids.alice),
+				setup.params.clone(),
+				state,
+				sig,
+				signer
+			),
+			pallet_perun::Error::<Test>::InvalidParticipantIndex
+		);
+	});
+}
+
+#[test]
+fn extension_conclude_final_stale() {
+	run_test(MOCK_APP, |setup| {
+		let mut state = setup.state.clone();
+		state.finalized = true;
+		let sigs = sign_state(&state, setup);
+		let call = pallet_perun::Call::conclude_final {
+			params: setup.params.clone(),
+			state: state.clone(),
+			state_sigs: sigs.clone(),
+		};
+		assert_eq!(validate(setup.ids.carl, call.clone()), Ok(0));
+
+		assert_ok!(Perun::conclude_final(
+			RuntimeOrigin::signed(setup.ids.carl),
+			setup.params.clone(),
+			state,
+			sigs
+		));
+		assert_eq!(
+			validate(setup.ids.carl, call),
+			Err(InvalidTransaction::Stale.into())
+		);
+	});
+}
+
+#[test]
+/// Other calls are not checked.
+fn extension_other_calls() {
+	run_test(MOCK_APP, |setup| {
+		let call = pallet_perun::Call::conclude {
+			params: setup.params.clone(),
+		};
+
+		assert_eq!(validate(setup.ids.carl, call), Ok(0));
+	});
+}
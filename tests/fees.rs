@@ -0,0 +1,192 @@
+//  Copyright 2021 PolyCrypt GmbH
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+mod common;
+use common::mock::*;
+use common::utils::*;
+
+use frame_support::{
+	assert_ok,
+	dispatch::{DispatchResultWithPostInfo, Pays},
+};
+
+/// Concludes the channel of `setup` with its finalized state on behalf
+/// of `who`.
+fn conclude_final_as(setup: &Setup, who: u64) -> DispatchResultWithPostInfo {
+	let mut state = setup.state.clone();
+	state.finalized = true;
+	Perun::conclude_final(
+		RuntimeOrigin::signed(who),
+		setup.params.clone(),
+		state.clone(),
+		sign_state(&state, setup),
+	)
+}
+
+/// Returns whether the fee of a call with result `res` is charged.
+fn pays(res: DispatchResultWithPostInfo) -> Pays {
+	match res {
+		Ok(info) => info.pays_fee,
+		Err(err) => err.post_info.pays_fee,
+	}
+}
+
+#[test]
+/// Only refutations of the bond of another account are free.
+fn fees_dispute() {
+	run_test(MOCK_APP, |setup| {
+		PerunDisputeBond::set(10);
+		let mut state = setup.state.clone();
+		assert_eq!(pays(dispute_as(setup, setup.ids.carl, &state)), Pays::Yes);
+		// Refuting the own bond is charged.
+		state.version += 1;
+		assert_eq!(pays(dispute_as(setup, setup.ids.carl, &state)), Pays::Yes);
+
+		state.version += 1;
+		assert_eq!(pays(dispute_as(setup, setup.ids.alice, &state)), Pays::No);
+		// Failed refutations are charged.
+		let res = dispute_as(setup, setup.ids.bob, &state);
+		assert!(res.is_err());
+		assert_eq!(pays(res), Pays::Yes);
+	});
+}
+
+#[test]
+/// Refutations are charged if no bond is required.
+fn fees_dispute_no_bond() {
+	run_test(MOCK_APP, |setup| {
+		let mut state = setup.state.clone();
+		assert_eq!(pays(dispute_as(setup, setup.ids.carl, &state)), Pays::Yes);
+		state.version += 1;
+		assert_eq!(pays(dispute_as(setup, setup.ids.alice, &state)), Pays::Yes);
+	});
+}
+
+#[test]
+/// Concluding an unfunded channel is charged.
+fn fees_conclude_unfunded() {
+	run_test(MOCK_APP, |setup| {
+		call_dispute(setup, false);
+		increment_time(2 * setup.params.challenge_duration);
+		assert_eq!(
+			pays(Perun::conclude(
+				RuntimeOrigin::signed(setup.ids.bob),
+				setup.params.clone()
+			)),
+			Pays::Yes
+		);
+	});
+
+	run_test(MOCK_APP, |setup| {
+		assert_eq!(pays(conclude_final_as(setup, setup.ids.bob)), Pays::Yes);
+		assert_event_concluded(setup.cid);
+	});
+}
+
+#[test]
+/// Concluding a funded channel is free, repeated conclusions are charged.
+fn fees_conclude() {
+	run_test(MOCK_APP, |setup| {
+		deposit_both(setup);
+		call_dispute(setup, false);
+		let res = Perun::conclude(RuntimeOrigin::signed(setup.ids.bob), setup.params.clone());
+		assert!(res.is_err());
+		assert_eq!(pays(res), Pays::Yes);
+
+		increment_time(2 * setup.params.challenge_duration);
+		let conclude =
+			|| Perun::conclude(RuntimeOrigin::signed(setup.ids.bob), setup.params.clone());
+		assert_eq!(pays(conclude()), Pays::No);
+		assert_event_concluded(setup.cid);
+		assert_eq!(pays(conclude()), Pays::Yes);
+	});
+}
+
+#[test]
+/// Concluding a funded channel with a final state is free, repeated
+/// conclusions are charged.
+fn fees_conclude_final() {
+	run_test(MOCK_APP, |setup| {
+		deposit_both(setup);
+		// Invalid states are charged.
+		let res = Perun::conclude_final(
+			RuntimeOrigin::signed(setup.ids.bob),
+			setup.params.clone(),
+			setup.state.clone(),
+			sign_state(&setup.state, setup),
+		);
+		assert!(res.is_err());
+		assert_eq!(pays(res), Pays::Yes);
+
+		assert_eq!(pays(conclude_final_as(setup, setup.ids.bob)), Pays::No);
+		assert_event_concluded(setup.cid);
+		assert_eq!(pays(conclude_final_as(setup, setup.ids.bob)), Pays::Yes);
+	});
+}
+
+#[test]
+fn fees_withdraw() {
+	run_test(MOCK_APP, |setup| {
+		deposit_both(setup);
+		assert_ok!(conclude_final_as(setup, setup.ids.alice));
+
+		let withdrawal = withdrawal(setup, &setup.keys.alice, setup.ids.alice);
+		let sigs = sign_withdrawal(&withdrawal, setup);
+		let withdraw = || {
+			Perun::withdraw(
+				RuntimeOrigin::signed(setup.ids.alice),
+				withdrawal.clone(),
+				sigs[0].clone(),
+			)
+		};
+		assert_eq!(pays(withdraw()), Pays::No);
+		event_withdrawn(setup.fids.alice);
+		// Withdrawing twice is charged.
+		let res = withdraw();
+		assert!(res.is_err());
+		assert_eq!(pays(res), Pays::Yes);
+	});
+}
+
+#[test]
+fn fees_withdraw_all() {
+	run_test(MOCK_APP, |setup| {
+		deposit_both(setup);
+		assert_ok!(conclude_final_as(setup, setup.ids.alice));
+
+		let withdrawals = vec![
+			withdrawal(setup, &setup.keys.alice, setup.ids.alice),
+			withdrawal(setup, &setup.keys.bob, setup.ids.bob),
+		];
+		let sigs: Vec<_> = withdrawals
+			.iter()
+			.enumerate()
+			.map(|(i, w)| sign_withdrawal(w, setup)[i].clone())
+			.collect();
+		let withdraw_all = || {
+			Perun::withdraw_all(
+				RuntimeOrigin::signed(setup.ids.carl),
+				setup.cid,
+				withdrawals.clone(),
+				sigs.clone(),
+			)
+		};
+		assert_eq!(pays(withdraw_all()), Pays::No);
+		event_withdrawn(setup.fids.bob);
+		// Withdrawing twice is charged.
+		let res = withdraw_all();
+		assert!(res.is_err());
+		assert_eq!(pays(res), Pays::Yes);
+	});
+}
@@ -0,0 +1,112 @@
+//  Copyright 2021 PolyCrypt GmbH
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+mod common;
+use common::mock::*;
+use common::utils::*;
+
+use frame_support::{assert_noop, assert_ok};
+use pallet_perun::types::{ParamsOf, StateOf};
+
+/// Account without balance that receives the outcome of bob.
+const EVE: u64 = 5;
+
+/// Returns funded params and a state of a channel that pays alice's
+/// outcome to dora and bob's outcome to eve.
+fn payout_channel(setup: &Setup, finalized: bool) -> (ParamsOf<Test>, StateOf<Test>) {
+	let mut params = setup.params.clone();
+	params.payouts = vec![setup.ids.dora, EVE];
+	let mut state = setup.state.clone();
+	state.channel_id = params.channel_id::<Test>().unwrap();
+	state.finalized = finalized;
+
+	for (i, who) in [setup.ids.alice, setup.ids.bob].into_iter().enumerate() {
+		let fid = Perun::calc_funding_id(state.channel_id, &params.participants[i]);
+		assert_ok!(Perun::deposit(
+			RuntimeOrigin::signed(who),
+			fid,
+			state.balances[i]
+		));
+	}
+	(params, state)
+}
+
+/// Asserts that the outcome of `state` was paid out.
+fn assert_paid_out(setup: &Setup, params: &ParamsOf<Test>, state: &StateOf<Test>) {
+	assert_eq!(
+		Balances::free_balance(setup.ids.dora),
+		1 + state.balances[0]
+	);
+	assert_eq!(Balances::free_balance(EVE), state.balances[1]);
+	for part in &params.participants {
+		let fid = Perun::calc_funding_id(state.channel_id, part);
+		assert_eq!(Perun::deposits(fid), None);
+	}
+}
+
+#[test]
+fn payout_conclude_final() {
+	run_test(MOCK_APP, |setup| {
+		let (params, state) = payout_channel(setup, true);
+
+		assert_ok!(Perun::conclude_final(
+			RuntimeOrigin::signed(setup.ids.carl),
+			params.clone(),
+			state.clone(),
+			sign_state(&state, setup)
+		));
+		assert_event_concluded(state.channel_id);
+		assert_paid_out(setup, &params, &state);
+	});
+}
+
+#[test]
+fn payout_conclude() {
+	run_test(NO_APP, |setup| {
+		let (params, state) = payout_channel(setup, false);
+		assert_ok!(Perun::dispute(
+			RuntimeOrigin::signed(setup.ids.carl),
+			params.clone(),
+			state.clone(),
+			sign_state(&state, setup)
+		));
+		increment_time(params.challenge_duration);
+
+		assert_ok!(Perun::conclude(
+			RuntimeOrigin::signed(setup.ids.carl),
+			params.clone()
+		));
+		assert_paid_out(setup, &params, &state);
+	});
+}
+
+#[test]
+fn payout_invalid() {
+	run_test(MOCK_APP, |setup| {
+		let mut params = setup.params.clone();
+		params.payouts = vec![setup.ids.dora];
+		let mut state = setup.state.clone();
+		state.channel_id = params.channel_id::<Test>().unwrap();
+
+		assert_noop!(
+			Perun::dispute(
+				RuntimeOrigin::signed(setup.ids.carl),
+				params,
+				state.clone(),
+				sign_state(&state, setup)
+			),
+			pallet_perun::Error::<Test>::InvalidPayouts
+		);
+	});
+}
@@ -46,6 +46,40 @@ fn progress() {
 	});
 }
 
+#[test]
+/// The app registry has access to the app params of the channel.
+fn progress_app_params() {
+	run_test(MOCK_APP, |setup| {
+		let mut params = setup.params.clone();
+		params.app_params = vec![7, 7].try_into().unwrap();
+		let mut state = setup.state.clone();
+		state.channel_id = params.channel_id::<Test>().unwrap();
+		let sigs = sign_state(&state, &setup);
+		assert_ok!(Perun::dispute(
+			RuntimeOrigin::signed(setup.ids.carl),
+			params.clone(),
+			state.clone(),
+			sigs
+		));
+
+		increment_time(setup.params.challenge_duration);
+
+		state.version += 1;
+		state.data = params.app_params.to_vec();
+		let sigs = sign_state(&state, &setup);
+
+		let signer = 0;
+		assert_ok!(Perun::progress(
+			RuntimeOrigin::signed(setup.ids.alice),
+			params.clone(),
+			state.clone(),
+			sigs[signer].clone(),
+			signer.try_into().unwrap(),
+		));
+		assert_event_progressed(state.channel_id, state.version, params.app);
+	});
+}
+
 #[test]
 fn progress_no_app() {
 	run_test(NO_APP, |setup| {
@@ -0,0 +1,107 @@
+//  Copyright 2021 PolyCrypt GmbH
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+mod common;
+use common::mock::*;
+use common::utils::*;
+
+use frame_support::{assert_noop, assert_ok};
+use pallet_perun::types::WithdrawalOf;
+use sp_core::crypto::Pair;
+
+/// Returns a withdrawal of alice that pays `fee` to `relayer`.
+fn relayed_withdrawal(setup: &Setup, fee: u64, relayer: Option<u64>) -> WithdrawalOf<Test> {
+	WithdrawalOf::<Test> {
+		channel_id: setup.cid,
+		receiver: setup.ids.alice,
+		part: setup.keys.alice.public(),
+		expiry: None,
+		splits: vec![],
+		fee,
+		relayer,
+	}
+}
+
+#[test]
+fn relay_fee_paid() {
+	run_test(MOCK_APP, |setup| {
+		conclude_funded(setup, RuntimeOrigin::signed(setup.ids.carl));
+		let carl = Balances::free_balance(setup.ids.carl);
+		let withdrawal = relayed_withdrawal(setup, 2, Some(setup.ids.carl));
+		let sigs = sign_withdrawal(&withdrawal, setup);
+
+		assert_ok!(Perun::withdraw(
+			RuntimeOrigin::signed(setup.ids.carl),
+			withdrawal,
+			sigs[0].clone()
+		));
+		event_withdrawn(setup.fids.alice);
+		assert_eq!(Balances::free_balance(setup.ids.carl), carl + 2);
+		assert_eq!(Balances::free_balance(setup.ids.alice), 90 + 8);
+	});
+}
+
+#[test]
+/// Anyone can relay a withdrawal without relayer and receives the fee.
+fn relay_any() {
+	run_test(MOCK_APP, |setup| {
+		conclude_funded(setup, RuntimeOrigin::signed(setup.ids.carl));
+		let withdrawal = relayed_withdrawal(setup, 2, None);
+		let sigs = sign_withdrawal(&withdrawal, setup);
+
+		assert_ok!(Perun::withdraw(
+			RuntimeOrigin::signed(setup.ids.dora),
+			withdrawal,
+			sigs[0].clone()
+		));
+		assert_eq!(Balances::free_balance(setup.ids.dora), 1 + 2);
+		assert_eq!(Balances::free_balance(setup.ids.alice), 90 + 8);
+	});
+}
+
+#[test]
+fn relay_invalid_relayer() {
+	run_test(MOCK_APP, |setup| {
+		conclude_funded(setup, RuntimeOrigin::signed(setup.ids.carl));
+		let withdrawal = relayed_withdrawal(setup, 2, Some(setup.ids.carl));
+		let sigs = sign_withdrawal(&withdrawal, setup);
+
+		assert_noop!(
+			Perun::withdraw(
+				RuntimeOrigin::signed(setup.ids.dora),
+				withdrawal,
+				sigs[0].clone()
+			),
+			pallet_perun::Error::<Test>::InvalidRelayer
+		);
+	});
+}
+
+#[test]
+fn relay_fee_too_high() {
+	run_test(MOCK_APP, |setup| {
+		conclude_funded(setup, RuntimeOrigin::signed(setup.ids.carl));
+		let withdrawal = relayed_withdrawal(setup, 11, None);
+		let sigs = sign_withdrawal(&withdrawal, setup);
+
+		assert_noop!(
+			Perun::withdraw(
+				RuntimeOrigin::signed(setup.ids.carl),
+				withdrawal,
+				sigs[0].clone()
+			),
+			pallet_perun::Error::<Test>::FeeTooHigh
+		);
+	});
+}
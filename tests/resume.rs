@@ -0,0 +1,108 @@
+//  Copyright 2021 PolyCrypt GmbH
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+mod common;
+use common::mock::*;
+use common::utils::*;
+
+use frame_support::{assert_noop, assert_ok};
+
+#[test]
+fn resume_ok() {
+	run_test(MOCK_APP, |setup| {
+		call_dispute(setup, false);
+		let state = next_state(setup);
+
+		assert_ok!(Perun::resume(
+			RuntimeOrigin::signed(setup.ids.carl),
+			setup.params.clone(),
+			state.clone(),
+			sign_state(&state, setup)
+		));
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::Perun(pallet_perun::Event::Resumed(setup.cid, state.version))
+		);
+		assert_eq!(Perun::state_registers(setup.cid), None);
+		assert_eq!(Perun::checkpoints(setup.cid), Some(state.version));
+
+		// The old dispute cannot be started again.
+		assert_noop!(
+			Perun::dispute(
+				RuntimeOrigin::signed(setup.ids.carl),
+				setup.params.clone(),
+				setup.state.clone(),
+				sign_state(&setup.state, setup)
+			),
+			pallet_perun::Error::<Test>::DisputeVersionTooLow
+		);
+	});
+}
+
+#[test]
+fn resume_version_too_low() {
+	run_test(MOCK_APP, |setup| {
+		call_dispute(setup, false);
+
+		assert_noop!(
+			Perun::resume(
+				RuntimeOrigin::signed(setup.ids.carl),
+				setup.params.clone(),
+				setup.state.clone(),
+				sign_state(&setup.state, setup)
+			),
+			pallet_perun::Error::<Test>::DisputeVersionTooLow
+		);
+	});
+}
+
+#[test]
+fn resume_unknown_channel() {
+	run_test(MOCK_APP, |setup| {
+		let state = next_state(setup);
+
+		assert_noop!(
+			Perun::resume(
+				RuntimeOrigin::signed(setup.ids.carl),
+				setup.params.clone(),
+				state.clone(),
+				sign_state(&state, setup)
+			),
+			pallet_perun::Error::<Test>::UnknownChannel
+		);
+	});
+}
+
+#[test]
+fn resume_concluded() {
+	run_test(MOCK_APP, |setup| {
+		call_dispute(setup, false);
+		increment_time(2 * setup.params.challenge_duration);
+		assert_ok!(Perun::conclude(
+			RuntimeOrigin::signed(setup.ids.carl),
+			setup.params.clone()
+		));
+		let state = next_state(setup);
+
+		assert_noop!(
+			Perun::resume(
+				RuntimeOrigin::signed(setup.ids.carl),
+				setup.params.clone(),
+				state.clone(),
+				sign_state(&state, setup)
+			),
+			pallet_perun::Error::<Test>::AlreadyConcluded
+		);
+	});
+}
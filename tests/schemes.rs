@@ -0,0 +1,283 @@
+//  Copyright 2021 PolyCrypt GmbH
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+//! Disputes with participants of each signature scheme.
+//!
+//! Uses its own runtime with [MultiSigner] participants since the common
+//! mock only supports ecdsa keys.
+
+use frame_support::{
+	assert_noop, assert_ok, derive_impl, parameter_types, weights::Weight, PalletId,
+};
+use pallet_perun::{
+	encoding::ScaleEncoding,
+	types::{
+		AppRegistry, ParamsOf, ParticipantIndex, SequentialVerifier, SignatureScheme, StateOf,
+	},
+};
+use sp_core::{crypto::Pair, ecdsa, ed25519, sr25519, ConstU32, ConstU64, H256};
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup},
+	BuildStorage, MultiSignature, MultiSigner, Perbill,
+};
+use sp_std::ops::Range;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system,
+		Balances: pallet_balances,
+		Timestamp: pallet_timestamp,
+		Perun: pallet_perun,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl frame_system::Config for Test {
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Nonce = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type DustRemoval = ();
+	type RuntimeEvent = RuntimeEvent;
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = frame_system::Pallet<Test>;
+	type WeightInfo = ();
+	type RuntimeHoldReason = ();
+	type RuntimeFreezeReason = ();
+	type FreezeIdentifier = u64;
+	type MaxFreezes = ();
+}
+
+impl pallet_timestamp::Config for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = ConstU64<1>;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const PerunPalletId: PalletId = PalletId(*b"prnstchs");
+	pub const PerunBondSlash: Perbill = Perbill::from_percent(50);
+	pub const PerunParticipantNum: Range<u32> = 1..256;
+	pub const PerunMaxAppParamsLen: u32 = 64;
+	pub const PerunDomainSeparation: bool = true;
+	pub const PerunSignatureScheme: SignatureScheme = SignatureScheme::Mixed;
+}
+impl pallet_perun::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type PalletId = PerunPalletId;
+	type MinDeposit = ConstU64<5>;
+	type DisputeBond = ConstU64<0>;
+	type BondSlash = PerunBondSlash;
+	type WatcherBond = ConstU64<20>;
+	type WatcherSlashPeriod = ConstU64<10>;
+	type ParticipantNum = PerunParticipantNum;
+	type Currency = Balances;
+	type Version = u32;
+	type Nonce = [u8; 32];
+	type Signature = MultiSignature;
+	type PK = MultiSigner;
+	type MaxThresholdKeys = ConstU32<1>;
+	type SignatureScheme = PerunSignatureScheme;
+	type Verifier = SequentialVerifier;
+	type AccountParticipants = ();
+	type Hasher = sp_core::KeccakHasher;
+	type HashValue = H256;
+	type Seconds = u64;
+	type WeightInfo = ();
+	type AppRegistry = NoApps;
+	type AppId = u64;
+	type NoApp = ConstU64<0>;
+	type MaxAppParamsLen = PerunMaxAppParamsLen;
+	type DomainSeparation = PerunDomainSeparation;
+	type Encoding = ScaleEncoding;
+	type Watchtower = ();
+	type UnsignedPriority = ConstU64<{ 1 << 20 }>;
+	type UnsignedLongevity = ConstU64<16>;
+	type MaxUnsignedPerBlock = ConstU32<8>;
+	type MaxExpiries = ConstU32<16>;
+	type UrgentPriority = ConstU64<{ 1 << 10 }>;
+	type UrgentPeriod = ConstU64<3>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = pallet_perun::benchmarking::AllSchemes;
+}
+
+/// Rejects all app transitions.
+pub struct NoApps;
+impl AppRegistry<Test> for NoApps {
+	fn valid_transition(
+		_: &ParamsOf<Test>,
+		_: &StateOf<Test>,
+		_: &StateOf<Test>,
+		_: ParticipantIndex,
+	) -> bool {
+		false
+	}
+
+	fn transition_weight(_: &ParamsOf<Test>) -> Weight {
+		Weight::zero()
+	}
+}
+
+/// Key pair of any signature scheme.
+trait SchemePair {
+	fn participant(&self) -> MultiSigner;
+	fn sign_payload(&self, msg: &[u8]) -> MultiSignature;
+}
+
+impl SchemePair for sr25519::Pair {
+	fn participant(&self) -> MultiSigner {
+		self.public().into()
+	}
+
+	fn sign_payload(&self, msg: &[u8]) -> MultiSignature {
+		self.sign(msg).into()
+	}
+}
+
+impl SchemePair for ed25519::Pair {
+	fn participant(&self) -> MultiSigner {
+		self.public().into()
+	}
+
+	fn sign_payload(&self, msg: &[u8]) -> MultiSignature {
+		self.sign(msg).into()
+	}
+}
+
+impl SchemePair for ecdsa::Pair {
+	fn participant(&self) -> MultiSigner {
+		self.public().into()
+	}
+
+	fn sign_payload(&self, msg: &[u8]) -> MultiSignature {
+		self.sign(msg).into()
+	}
+}
+
+/// Runs `test` in a fresh runtime.
+fn run_test(test: impl FnOnce()) {
+	let mut ext: sp_io::TestExternalities = RuntimeGenesisConfig {
+		system: Default::default(),
+		balances: pallet_balances::GenesisConfig::<Test> {
+			balances: vec![(1, 100)],
+		},
+	}
+	.build_storage()
+	.unwrap()
+	.into();
+	// Start at block 1 to enable event emission.
+	ext.execute_with(|| System::set_block_number(1));
+	ext.execute_with(test)
+}
+
+/// Disputes a channel of `parts` with signatures of all of them.
+fn dispute_with(parts: &[&dyn SchemePair]) {
+	let params = ParamsOf::<Test> {
+		nonce: [7; 32],
+		participants: parts.iter().map(|p| p.participant()).collect(),
+		challenge_duration: 10,
+		app: 0,
+		app_params: Default::default(),
+		payouts: vec![],
+	};
+	let state = StateOf::<Test> {
+		channel_id: params.channel_id::<Test>().unwrap(),
+		version: 1,
+		balances: vec![10; parts.len()],
+		finalized: false,
+		data: vec![],
+	};
+	let msg = Perun::state_payload(&state).unwrap();
+	let mut sigs: Vec<MultiSignature> = parts.iter().map(|p| p.sign_payload(&msg)).collect();
+
+	// A signature of another participant is rejected.
+	sigs.rotate_left(1);
+	assert_noop!(
+		Perun::dispute(
+			RuntimeOrigin::signed(1),
+			params.clone(),
+			state.clone(),
+			sigs.clone()
+		),
+		pallet_perun::Error::<Test>::InvalidSignature
+	);
+	sigs.rotate_right(1);
+	assert_ok!(Perun::dispute(
+		RuntimeOrigin::signed(1),
+		params,
+		state.clone(),
+		sigs
+	));
+	assert_eq!(
+		System::events().last().unwrap().event,
+		RuntimeEvent::Perun(pallet_perun::Event::Disputed(state.channel_id, state))
+	);
+}
+
+#[test]
+fn scheme_sr25519() {
+	run_test(|| {
+		let alice = sr25519::Pair::from_string("//Alice", None).unwrap();
+		let bob = sr25519::Pair::from_string("//Bob", None).unwrap();
+		dispute_with(&[&alice, &bob]);
+	});
+}
+
+#[test]
+fn scheme_ed25519() {
+	run_test(|| {
+		let alice = ed25519::Pair::from_string("//Alice", None).unwrap();
+		let bob = ed25519::Pair::from_string("//Bob", None).unwrap();
+		dispute_with(&[&alice, &bob]);
+	});
+}
+
+#[test]
+fn scheme_ecdsa() {
+	run_test(|| {
+		let alice = ecdsa::Pair::from_string("//Alice", None).unwrap();
+		let bob = ecdsa::Pair::from_string("//Bob", None).unwrap();
+		dispute_with(&[&alice, &bob]);
+	});
+}
+
+#[test]
+/// The participants of one channel can use different schemes.
+fn scheme_mixed() {
+	run_test(|| {
+		let alice = sr25519::Pair::from_string("//Alice", None).unwrap();
+		let bob = ed25519::Pair::from_string("//Bob", None).unwrap();
+		let carl = ecdsa::Pair::from_string("//Carl", None).unwrap();
+		dispute_with(&[&alice, &bob, &carl]);
+	});
+}
@@ -0,0 +1,139 @@
+//  Copyright 2021 PolyCrypt GmbH
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use codec::Encode;
+use pallet_perun::{
+	eth::{eth_message_hash, EthAddress, EthSignature},
+	types::{separated, verify_sig, BatchVerifier, SequentialVerifier, State},
+};
+use sp_core::{crypto::Pair, ecdsa, ed25519, sr25519, H256};
+use sp_runtime::{traits::Verify, MultiSignature, MultiSigner};
+
+/// Ethereum test key and its address.
+const ETH_SK: &str = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+const ETH_ADDRESS: &str = "2c7536e3605d9c16a7a3d7b1898e529396a65c23";
+
+/// Creates a state that is not bound to any runtime.
+fn new_state() -> State<H256, u64, u64> {
+	State {
+		channel_id: H256::repeat_byte(1),
+		version: 1,
+		balances: vec![10, 5, 3],
+		finalized: false,
+		data: vec![],
+	}
+}
+
+#[test]
+/// Each participant is verified with its own signature scheme.
+fn multi_signature_schemes() {
+	let state = new_state();
+	let msg = state.encode();
+	let sr = sr25519::Pair::from_string("//Alice", None).unwrap();
+	let ed = ed25519::Pair::from_string("//Bob", None).unwrap();
+	let ec = ecdsa::Pair::from_string("//Carl", None).unwrap();
+
+	let parts: Vec<MultiSigner> = vec![sr.public().into(), ed.public().into(), ec.public().into()];
+	let sigs: Vec<MultiSignature> = vec![
+		sr.sign(&msg).into(),
+		ed.sign(&msg).into(),
+		ec.sign(&msg).into(),
+	];
+
+	for (sig, part) in sigs.iter().zip(parts.iter()) {
+		assert!(verify_sig(&msg, sig, part));
+	}
+	// A signature does not verify for a participant of another scheme.
+	for (i, sig) in sigs.iter().enumerate() {
+		for (j, part) in parts.iter().enumerate() {
+			if i != j {
+				assert!(!verify_sig(&msg, sig, part));
+			}
+		}
+	}
+}
+
+#[test]
+/// All signatures must be valid and belong to their participant.
+fn sequential_verifier() {
+	let msg = new_state().encode();
+	let pairs: Vec<_> = ["//Alice", "//Bob", "//Carl"]
+		.iter()
+		.map(|s| sr25519::Pair::from_string(s, None).unwrap())
+		.collect();
+	let parts: Vec<MultiSigner> = pairs.iter().map(|p| p.public().into()).collect();
+	let mut sigs: Vec<MultiSignature> = pairs.iter().map(|p| p.sign(&msg).into()).collect();
+
+	assert!(SequentialVerifier::verify_all(&msg, &sigs, &parts));
+	assert!(!SequentialVerifier::verify_all(&msg[1..], &sigs, &parts));
+	assert!(!SequentialVerifier::verify_all(&msg, &sigs[1..], &parts));
+	sigs.swap(0, 1);
+	assert!(!SequentialVerifier::verify_all(&msg, &sigs, &parts));
+}
+
+#[test]
+/// A signature does not verify for a different domain.
+fn multi_signature_domain() {
+	let state = new_state();
+	let sr = sr25519::Pair::from_string("//Alice", None).unwrap();
+	let part: MultiSigner = sr.public().into();
+	let msg = separated(b"domain", &state.encode());
+	let sig: MultiSignature = sr.sign(&msg).into();
+
+	assert!(verify_sig(&msg, &sig, &part));
+	assert!(!verify_sig(&state.encode(), &sig, &part));
+}
+
+#[test]
+/// Verifies a signature that was created by an Ethereum wallet.
+fn eth_golden_vector() {
+	let msg = b"perun";
+	let hash = "69348ad00aaed49183fac61e70271a0dfae49f6dacb3d94a617e89edf20b46ed";
+	let sig = "e3672be5bb8086a954bc1a985236bdba4b5c53099b1a30bcf4a5db5a62327164\
+	           7ea2934dc493f451c94f762ec46633b4c62bb9dfc73e41d5b65db8ed5850ab291c";
+
+	assert_eq!(eth_message_hash(msg).to_vec(), from_hex(hash));
+	let sig = EthSignature(from_hex(sig).try_into().unwrap());
+	assert!(sig.verify(&msg[..], &eth_address()));
+	assert!(!sig.verify(&b"other"[..], &eth_address()));
+	assert!(!sig.verify(&msg[..], &EthAddress::default()));
+}
+
+#[test]
+/// A state is signed over the EIP-191 prefixed keccak hash of its payload.
+fn eth_signature_state() {
+	let msg = new_state().encode();
+	let pair = ecdsa::Pair::from_seed(&from_hex(ETH_SK).try_into().unwrap());
+	let sig: EthSignature = pair.sign_prehashed(&eth_message_hash(&msg)).into();
+
+	assert!(verify_sig(&msg, &sig, &eth_address()));
+	assert!(!verify_sig(&msg, &sig, &EthAddress::default()));
+	// Signing the payload without the EIP-191 hash is not valid.
+	let sig: EthSignature = pair.sign(&msg).into();
+	assert!(!verify_sig(&msg, &sig, &eth_address()));
+}
+
+/// Returns the Ethereum address of `ETH_SK`.
+fn eth_address() -> EthAddress {
+	EthAddress(from_hex(ETH_ADDRESS).try_into().unwrap())
+}
+
+/// Decodes a hex string without `0x` prefix.
+fn from_hex(hex: &str) -> Vec<u8> {
+	let hex: String = hex.split_whitespace().collect();
+	(0..hex.len())
+		.step_by(2)
+		.map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+		.collect()
+}
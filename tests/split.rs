@@ -0,0 +1,96 @@
+//  Copyright 2021 PolyCrypt GmbH
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+mod common;
+use common::mock::*;
+use common::utils::*;
+
+use frame_support::{assert_noop, assert_ok};
+use pallet_perun::types::WithdrawalOf;
+use sp_core::crypto::Pair;
+
+/// Account without balance that receives a split.
+const EVE: u64 = 5;
+
+/// Returns a withdrawal of alice that pays `splits` and the rest to her.
+fn split_withdrawal(setup: &Setup, splits: Vec<(u64, u64)>) -> WithdrawalOf<Test> {
+	WithdrawalOf::<Test> {
+		channel_id: setup.cid,
+		receiver: setup.ids.alice,
+		part: setup.keys.alice.public(),
+		expiry: None,
+		splits,
+		fee: 0,
+		relayer: None,
+	}
+}
+
+#[test]
+fn split_ok() {
+	run_test(MOCK_APP, |setup| {
+		conclude_funded(setup, RuntimeOrigin::signed(setup.ids.carl));
+		let carl = Balances::free_balance(setup.ids.carl);
+		let withdrawal = split_withdrawal(setup, vec![(setup.ids.carl, 3), (EVE, 2)]);
+		let sigs = sign_withdrawal(&withdrawal, setup);
+
+		assert_ok!(Perun::withdraw(
+			RuntimeOrigin::signed(setup.ids.alice),
+			withdrawal,
+			sigs[0].clone()
+		));
+		event_withdrawn(setup.fids.alice);
+		assert_eq!(Balances::free_balance(setup.ids.carl), carl + 3);
+		assert_eq!(Balances::free_balance(EVE), 2);
+		assert_eq!(Balances::free_balance(setup.ids.alice), 90 + 5);
+		assert_eq!(Perun::deposits(setup.fids.alice), None);
+	});
+}
+
+#[test]
+/// The splits must not exceed the outcome.
+fn split_exceeds_outcome() {
+	run_test(MOCK_APP, |setup| {
+		conclude_funded(setup, RuntimeOrigin::signed(setup.ids.carl));
+		let withdrawal = split_withdrawal(setup, vec![(setup.ids.carl, 6), (EVE, 5)]);
+		let sigs = sign_withdrawal(&withdrawal, setup);
+
+		assert_noop!(
+			Perun::withdraw(
+				RuntimeOrigin::signed(setup.ids.alice),
+				withdrawal,
+				sigs[0].clone()
+			),
+			pallet_perun::Error::<Test>::InvalidSplit
+		);
+	});
+}
+
+#[test]
+/// The splits are covered by the signature.
+fn split_invalid_sig() {
+	run_test(MOCK_APP, |setup| {
+		conclude_funded(setup, RuntimeOrigin::signed(setup.ids.carl));
+		let sigs = sign_withdrawal(&split_withdrawal(setup, vec![]), setup);
+		let withdrawal = split_withdrawal(setup, vec![(setup.ids.carl, 10)]);
+
+		assert_noop!(
+			Perun::withdraw(
+				RuntimeOrigin::signed(setup.ids.carl),
+				withdrawal,
+				sigs[0].clone()
+			),
+			pallet_perun::Error::<Test>::InvalidSignature
+		);
+	});
+}
@@ -0,0 +1,174 @@
+//  Copyright 2021 PolyCrypt GmbH
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use codec::{Decode, Encode};
+use pallet_perun::{
+	threshold::{ThresholdKey, ThresholdSignature},
+	types::{verify_sig, State},
+};
+use sp_core::{crypto::Pair, sr25519, ConstU32, H256};
+
+type Key = ThresholdKey<sr25519::Public, ConstU32<4>>;
+type Sig = ThresholdSignature<sr25519::Signature, ConstU32<4>>;
+
+fn new_msg() -> Vec<u8> {
+	State::<H256, u64, u64> {
+		channel_id: H256::repeat_byte(1),
+		version: 1,
+		balances: vec![10, 5],
+		finalized: false,
+		data: vec![],
+	}
+	.encode()
+}
+
+fn new_pairs() -> Vec<sr25519::Pair> {
+	["//Alice", "//Bob", "//Carl"]
+		.iter()
+		.map(|s| sr25519::Pair::from_string(s, None).unwrap())
+		.collect()
+}
+
+/// Returns a 2-of-3 key set of `pairs`.
+fn new_key(pairs: &[sr25519::Pair]) -> Key {
+	ThresholdKey::Threshold {
+		threshold: 2,
+		keys: pairs
+			.iter()
+			.map(|p| p.public())
+			.collect::<Vec<_>>()
+			.try_into()
+			.unwrap(),
+	}
+}
+
+/// Lets the members at `indices` sign `msg`.
+fn sign(pairs: &[sr25519::Pair], indices: &[u32], msg: &[u8]) -> Sig {
+	ThresholdSignature::Threshold(
+		indices
+			.iter()
+			.map(|i| (*i, pairs[*i as usize].sign(msg)))
+			.collect::<Vec<_>>()
+			.try_into()
+			.unwrap(),
+	)
+}
+
+#[test]
+fn threshold_reached() {
+	let (pairs, msg) = (new_pairs(), new_msg());
+	let key = new_key(&pairs);
+
+	assert!(verify_sig(&msg, &sign(&pairs, &[0, 2], &msg), &key));
+	assert!(verify_sig(&msg, &sign(&pairs, &[0, 1, 2], &msg), &key));
+	assert!(!verify_sig(&msg[1..], &sign(&pairs, &[0, 2], &msg), &key));
+}
+
+#[test]
+fn threshold_not_reached() {
+	let (pairs, msg) = (new_pairs(), new_msg());
+	let key = new_key(&pairs);
+
+	assert!(!verify_sig(&msg, &sign(&pairs, &[1], &msg), &key));
+	// A member can only sign once.
+	assert!(!verify_sig(&msg, &sign(&pairs, &[1, 1], &msg), &key));
+	assert!(!verify_sig(&msg, &sign(&pairs, &[2, 1], &msg), &key));
+	// The index must belong to the signing member.
+	let sig: Sig = ThresholdSignature::Threshold(
+		vec![(0, pairs[0].sign(&msg)), (1, pairs[2].sign(&msg))]
+			.try_into()
+			.unwrap(),
+	);
+	assert!(!verify_sig(&msg, &sig, &key));
+	let sig: Sig = ThresholdSignature::Threshold(
+		vec![(0, pairs[0].sign(&msg)), (3, pairs[2].sign(&msg))]
+			.try_into()
+			.unwrap(),
+	);
+	assert!(!verify_sig(&msg, &sig, &key));
+}
+
+#[test]
+fn threshold_zero() {
+	let (pairs, msg) = (new_pairs(), new_msg());
+	let key: Key = ThresholdKey::Threshold {
+		threshold: 0,
+		keys: pairs
+			.iter()
+			.map(|p| p.public())
+			.collect::<Vec<_>>()
+			.try_into()
+			.unwrap(),
+	};
+
+	assert!(!verify_sig(&msg, &sign(&pairs, &[], &msg), &key));
+}
+
+#[test]
+fn threshold_single() {
+	let (pairs, msg) = (new_pairs(), new_msg());
+	let key: Key = ThresholdKey::Single(pairs[0].public());
+	let sig: Sig = ThresholdSignature::Single(pairs[0].sign(&msg));
+
+	assert!(verify_sig(&msg, &sig, &key));
+	assert!(!verify_sig(
+		&msg,
+		&sig,
+		&ThresholdKey::Single(pairs[1].public())
+	));
+	// Single signatures are not valid for key sets and vice versa.
+	assert!(!verify_sig(&msg, &sig, &new_key(&pairs)));
+	assert!(!verify_sig(&msg, &sign(&pairs, &[0], &msg), &key));
+}
+
+#[test]
+/// The encoding of a key set commits to its threshold and members.
+fn threshold_commitment() {
+	let pairs = new_pairs();
+	let key = new_key(&pairs);
+	let other: Key = ThresholdKey::Threshold {
+		threshold: 1,
+		keys: pairs
+			.iter()
+			.map(|p| p.public())
+			.collect::<Vec<_>>()
+			.try_into()
+			.unwrap(),
+	};
+
+	assert_ne!(key.encode(), other.encode());
+	assert_ne!(key.encode(), new_key(&pairs[..2]).encode());
+}
+
+#[test]
+/// Key sets and signatures with more than `MaxKeys` members do not decode.
+fn threshold_too_many_keys() {
+	let pairs: Vec<_> = (0..5)
+		.map(|i| sr25519::Pair::from_string(&format!("//{}", i), None).unwrap())
+		.collect();
+	let msg = new_msg();
+	let keys: Vec<_> = pairs.iter().map(|p| p.public()).collect();
+	let sigs: Vec<_> = pairs
+		.iter()
+		.enumerate()
+		.map(|(i, p)| (i as u32, p.sign(&msg)))
+		.collect();
+
+	// Same encoding as the bounded variants.
+	let key = (1u8, 3u32, keys).encode();
+	let sig = (1u8, sigs).encode();
+	assert!(Key::decode(&mut &key[..]).is_err());
+	assert!(Sig::decode(&mut &sig[..]).is_err());
+	assert!(Key::decode(&mut &new_key(&pairs[..4]).encode()[..]).is_ok());
+}
@@ -16,12 +16,15 @@ mod common;
 use common::mock::*;
 use common::utils::*;
 
-use frame_support::assert_noop;
+use codec::Encode;
+use frame_support::{assert_noop, assert_ok};
+use pallet_perun::types::{Funding, HasherOf};
 #[cfg(feature = "expose_privates")]
 use pallet_perun::{
 	types::{BalanceOf, PkOf},
 	Error,
 };
+use sp_core::{crypto::Pair, Hasher};
 use sp_runtime::traits::BadOrigin;
 
 #[cfg(feature = "expose_privates")]
@@ -72,39 +75,71 @@ fn time_now() {
 }
 
 #[test]
-/// All functions need signed origins.
-fn unsigned_tx() {
-	run_test(MOCK_APP, |_| {
+/// States that are signed without domain separator are only accepted if
+/// domain separation is disabled.
+fn domain_separation_state() {
+	run_test(MOCK_APP, |setup| {
+		let raw = Encode::encode(&setup.state);
+		let sigs = vec![setup.keys.alice.sign(&raw), setup.keys.bob.sign(&raw)];
+
 		assert_noop!(
-			Perun::deposit(
-				RuntimeOrigin::none(),
-				Default::default(),
-				Default::default()
+			Perun::dispute(
+				RuntimeOrigin::signed(setup.ids.carl),
+				setup.params.clone(),
+				setup.state.clone(),
+				sigs.clone()
 			),
-			BadOrigin
+			pallet_perun::Error::<Test>::InvalidSignature
 		);
+
+		PerunDomainSeparation::set(false);
+		assert_ok!(Perun::dispute(
+			RuntimeOrigin::signed(setup.ids.carl),
+			setup.params.clone(),
+			setup.state.clone(),
+			sigs
+		));
+	});
+}
+
+#[test]
+/// Funding ids are only calculated from the plain encoding if domain
+/// separation is disabled.
+fn domain_separation_funding_id() {
+	run_test(MOCK_APP, |setup| {
+		let part = &setup.params.participants[0];
+		let plain = HasherOf::<Test>::hash(
+			&Funding {
+				channel: setup.cid,
+				part,
+			}
+			.encode(),
+		);
+		assert_ne!(plain, setup.fids.alice);
+
+		PerunDomainSeparation::set(false);
+		assert_eq!(plain, Perun::calc_funding_id(setup.cid, part));
 	});
+}
 
+#[test]
+/// All functions need signed origins, except for the unsigned calls in
+/// `tests/unsigned.rs`.
+fn unsigned_tx() {
 	run_test(MOCK_APP, |_| {
 		assert_noop!(
-			Perun::dispute(
+			Perun::deposit(
 				RuntimeOrigin::none(),
 				Default::default(),
-				Default::default(),
 				Default::default()
 			),
 			BadOrigin
 		);
 	});
+
 	run_test(MOCK_APP, |_| {
 		assert_noop!(
-			Perun::conclude(RuntimeOrigin::none(), Default::default(),),
-			BadOrigin
-		);
-	});
-	run_test(MOCK_APP, |_| {
-		assert_noop!(
-			Perun::conclude_final(
+			Perun::deposit_for(
 				RuntimeOrigin::none(),
 				Default::default(),
 				Default::default(),
@@ -115,11 +150,7 @@ fn unsigned_tx() {
 	});
 	run_test(MOCK_APP, |_| {
 		assert_noop!(
-			Perun::withdraw(
-				RuntimeOrigin::none(),
-				Default::default(),
-				Default::default()
-			),
+			Perun::conclude(RuntimeOrigin::none(), Default::default(),),
 			BadOrigin
 		);
 	});
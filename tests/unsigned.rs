@@ -0,0 +1,384 @@
+//  Copyright 2021 PolyCrypt GmbH
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+mod common;
+use common::mock::*;
+use common::utils::*;
+
+use frame_support::{assert_noop, assert_ok};
+use pallet_perun::types::{StateOf, WithdrawalOf};
+use sp_runtime::{
+	traits::ValidateUnsigned,
+	transaction_validity::{
+		InvalidTransaction, TransactionSource, TransactionValidity, TransactionValidityError,
+	},
+	DispatchError,
+};
+
+const BOND: u64 = 10;
+
+/// Validates `call` as unsigned transaction.
+fn validate(call: pallet_perun::Call<Test>) -> TransactionValidity {
+	Perun::validate_unsigned(TransactionSource::External, &call)
+}
+
+/// Returns the invalid transaction error that `err` is mapped to.
+fn invalid(err: pallet_perun::Error<Test>) -> TransactionValidityError {
+	match DispatchError::from(err) {
+		DispatchError::Module(err) => InvalidTransaction::Custom(err.error[0]).into(),
+		_ => unreachable!(),
+	}
+}
+
+fn dispute_call(setup: &Setup, state: &StateOf<Test>) -> pallet_perun::Call<Test> {
+	pallet_perun::Call::dispute {
+		params: setup.params.clone(),
+		state: state.clone(),
+		state_sigs: sign_state(state, setup),
+	}
+}
+
+/// Returns the finalized state of `setup`.
+fn final_state(setup: &Setup) -> StateOf<Test> {
+	let mut state = setup.state.clone();
+	state.finalized = true;
+	state
+}
+
+/// Funds the channel of `setup` and disputes it with a signed origin.
+fn fund_and_dispute(setup: &Setup) {
+	deposit_both(setup);
+	call_dispute(setup, false);
+}
+
+#[test]
+fn unsigned_dispute() {
+	run_test(MOCK_APP, |setup| {
+		fund_and_dispute(setup);
+		let state = next_state(setup);
+		let valid = validate(dispute_call(setup, &state)).unwrap();
+		assert_eq!(valid.priority, PerunUnsignedPriority::get());
+		assert_eq!(valid.longevity, PerunUnsignedLongevity::get());
+		assert!(valid.propagate);
+
+		assert_ok!(Perun::dispute(
+			RuntimeOrigin::none(),
+			setup.params.clone(),
+			state.clone(),
+			sign_state(&state, setup)
+		));
+		assert_event_disputed(setup.cid, state.clone());
+		// The same version is not accepted twice.
+		increment_time(1);
+		assert_eq!(
+			validate(dispute_call(setup, &state)),
+			Err(invalid(pallet_perun::Error::<Test>::DisputeVersionTooLow))
+		);
+	});
+}
+
+#[test]
+/// Unsigned disputes cannot register the first state of a channel.
+fn unsigned_registration() {
+	run_test(MOCK_APP, |setup| {
+		deposit_both(setup);
+
+		assert_eq!(
+			validate(dispute_call(setup, &setup.state)),
+			Err(invalid(pallet_perun::Error::<Test>::UnsignedRegistration))
+		);
+		assert_noop!(
+			Perun::dispute(
+				RuntimeOrigin::none(),
+				setup.params.clone(),
+				setup.state.clone(),
+				sign_state(&setup.state, setup)
+			),
+			pallet_perun::Error::<Test>::UnsignedRegistration
+		);
+	});
+}
+
+#[test]
+/// Unsigned disputes and final conclusions need a funded channel.
+fn unsigned_unfunded() {
+	run_test(MOCK_APP, |setup| {
+		call_dispute(setup, false);
+		let state = next_state(setup);
+		assert_eq!(
+			validate(dispute_call(setup, &state)),
+			Err(invalid(pallet_perun::Error::<Test>::UnsignedUnfunded))
+		);
+		assert_noop!(
+			Perun::dispute(
+				RuntimeOrigin::none(),
+				setup.params.clone(),
+				state.clone(),
+				sign_state(&state, setup)
+			),
+			pallet_perun::Error::<Test>::UnsignedUnfunded
+		);
+
+		let state = final_state(setup);
+		assert_noop!(
+			Perun::conclude_final(
+				RuntimeOrigin::none(),
+				setup.params.clone(),
+				state.clone(),
+				sign_state(&state, setup)
+			),
+			pallet_perun::Error::<Test>::UnsignedUnfunded
+		);
+	});
+}
+
+#[test]
+/// Only [PerunMaxUnsignedPerBlock] unsigned calls are accepted per block.
+fn unsigned_limit() {
+	run_test(MOCK_APP, |setup| {
+		PerunMaxUnsignedPerBlock::set(1);
+		fund_and_dispute(setup);
+		let state = next_state(setup);
+		assert_ok!(Perun::dispute(
+			RuntimeOrigin::none(),
+			setup.params.clone(),
+			state.clone(),
+			sign_state(&state, setup)
+		));
+
+		let state = final_state(setup);
+		let call = pallet_perun::Call::conclude_final {
+			params: setup.params.clone(),
+			state: state.clone(),
+			state_sigs: sign_state(&state, setup),
+		};
+		assert_eq!(
+			validate(call.clone()),
+			Err(InvalidTransaction::ExhaustsResources.into())
+		);
+		assert_noop!(
+			Perun::conclude_final(
+				RuntimeOrigin::none(),
+				setup.params.clone(),
+				state.clone(),
+				sign_state(&state, setup)
+			),
+			pallet_perun::Error::<Test>::UnsignedLimit
+		);
+		// The limit is per block.
+		increment_time(1);
+		assert_ok!(validate(call));
+	});
+}
+
+#[test]
+/// A channel is only disputed unsigned once per block.
+fn unsigned_dispute_once_per_block() {
+	run_test(MOCK_APP, |setup| {
+		fund_and_dispute(setup);
+		let first = next_state(setup);
+		let mut state = first.clone();
+		state.version += 1;
+		// Disputes of a channel provide the same tag.
+		assert_eq!(
+			validate(dispute_call(setup, &first)).unwrap().provides,
+			validate(dispute_call(setup, &state)).unwrap().provides
+		);
+
+		assert_ok!(Perun::dispute(
+			RuntimeOrigin::none(),
+			setup.params.clone(),
+			first.clone(),
+			sign_state(&first, setup)
+		));
+		assert_eq!(
+			validate(dispute_call(setup, &state)),
+			Err(invalid(pallet_perun::Error::<Test>::UnsignedDisputed))
+		);
+		assert_noop!(
+			Perun::dispute(
+				RuntimeOrigin::none(),
+				setup.params.clone(),
+				state.clone(),
+				sign_state(&state, setup)
+			),
+			pallet_perun::Error::<Test>::UnsignedDisputed
+		);
+
+		// Signed refutations are not limited.
+		assert_ok!(Perun::dispute(
+			RuntimeOrigin::signed(setup.ids.carl),
+			setup.params.clone(),
+			state.clone(),
+			sign_state(&state, setup)
+		));
+		state.version += 1;
+		increment_time(1);
+		assert_ok!(validate(dispute_call(setup, &state)));
+	});
+}
+
+#[test]
+fn unsigned_dispute_invalid_sig() {
+	run_test(MOCK_APP, |setup| {
+		fund_and_dispute(setup);
+		let state = next_state(setup);
+		let mut sigs = sign_state(&state, setup);
+		sigs[0] = sigs[1].clone();
+		let call = pallet_perun::Call::dispute {
+			params: setup.params.clone(),
+			state,
+			state_sigs: sigs,
+		};
+
+		assert_eq!(
+			validate(call),
+			Err(invalid(pallet_perun::Error::<Test>::InvalidSignature))
+		);
+	});
+}
+
+#[test]
+/// An unsigned refutation burns the slashed part of the bond.
+fn unsigned_refutation_burns_slash() {
+	run_test(MOCK_APP, |setup| {
+		PerunDisputeBond::set(BOND);
+		let carl = Balances::free_balance(setup.ids.carl);
+		fund_and_dispute(setup);
+		let issuance = Balances::total_issuance();
+
+		let state = next_state(setup);
+		assert_ok!(validate(dispute_call(setup, &state)));
+		assert_ok!(Perun::dispute(
+			RuntimeOrigin::none(),
+			setup.params.clone(),
+			state.clone(),
+			sign_state(&state, setup)
+		));
+
+		assert_eq!(Balances::free_balance(setup.ids.carl), carl - BOND / 2);
+		assert_eq!(Balances::total_issuance(), issuance - BOND / 2);
+		assert_eq!(Perun::bonds(setup.cid), None);
+	});
+}
+
+#[test]
+fn unsigned_conclude_final() {
+	run_test(MOCK_APP, |setup| {
+		let state = final_state(setup);
+		let call = pallet_perun::Call::conclude_final {
+			params: setup.params.clone(),
+			state: state.clone(),
+			state_sigs: sign_state(&state, setup),
+		};
+		assert_eq!(
+			validate(call.clone()),
+			Err(invalid(pallet_perun::Error::<Test>::UnsignedUnfunded))
+		);
+
+		conclude_funded(setup, RuntimeOrigin::none());
+		assert_eq!(validate(call), Err(InvalidTransaction::Stale.into()));
+	});
+}
+
+#[test]
+fn unsigned_withdraw() {
+	run_test(MOCK_APP, |setup| {
+		conclude_funded(setup, RuntimeOrigin::none());
+		let withdrawal = withdrawal(setup, &setup.keys.alice, setup.ids.alice);
+		let sigs = sign_withdrawal(&withdrawal, setup);
+		let call = pallet_perun::Call::withdraw {
+			withdrawal: withdrawal.clone(),
+			withdrawal_sig: sigs[0].clone(),
+		};
+		assert_ok!(validate(call.clone()));
+
+		assert_ok!(Perun::withdraw(
+			RuntimeOrigin::none(),
+			withdrawal,
+			sigs[0].clone()
+		));
+		event_withdrawn(setup.fids.alice);
+		assert_eq!(Balances::free_balance(setup.ids.alice), 100);
+		assert_eq!(
+			validate(call),
+			Err(invalid(pallet_perun::Error::<Test>::UnknownDeposit))
+		);
+	});
+}
+
+#[test]
+/// An unsigned withdrawal cannot pay a fee.
+fn unsigned_withdraw_fee() {
+	run_test(MOCK_APP, |setup| {
+		conclude_funded(setup, RuntimeOrigin::none());
+		let withdrawal = WithdrawalOf::<Test> {
+			fee: 2,
+			..withdrawal(setup, &setup.keys.alice, setup.ids.alice)
+		};
+		let sigs = sign_withdrawal(&withdrawal, setup);
+		let call = pallet_perun::Call::withdraw {
+			withdrawal: withdrawal.clone(),
+			withdrawal_sig: sigs[0].clone(),
+		};
+
+		assert_eq!(
+			validate(call),
+			Err(invalid(pallet_perun::Error::<Test>::UnsignedFee))
+		);
+		assert_noop!(
+			Perun::withdraw(RuntimeOrigin::none(), withdrawal, sigs[0].clone()),
+			pallet_perun::Error::<Test>::UnsignedFee
+		);
+	});
+}
+
+#[test]
+/// A withdrawal for a relayer must be submitted by the relayer.
+fn unsigned_withdraw_relayer() {
+	run_test(MOCK_APP, |setup| {
+		conclude_funded(setup, RuntimeOrigin::none());
+		let withdrawal = WithdrawalOf::<Test> {
+			fee: 2,
+			relayer: Some(setup.ids.carl),
+			..withdrawal(setup, &setup.keys.alice, setup.ids.alice)
+		};
+		let sigs = sign_withdrawal(&withdrawal, setup);
+		let call = pallet_perun::Call::withdraw {
+			withdrawal: withdrawal.clone(),
+			withdrawal_sig: sigs[0].clone(),
+		};
+
+		assert_eq!(
+			validate(call),
+			Err(invalid(pallet_perun::Error::<Test>::InvalidRelayer))
+		);
+		assert_noop!(
+			Perun::withdraw(RuntimeOrigin::none(), withdrawal, sigs[0].clone()),
+			pallet_perun::Error::<Test>::InvalidRelayer
+		);
+	});
+}
+
+#[test]
+/// Only disputes, final conclusions and withdrawals are accepted unsigned.
+fn unsigned_other_calls() {
+	run_test(MOCK_APP, |setup| {
+		let call = pallet_perun::Call::conclude {
+			params: setup.params.clone(),
+		};
+
+		assert_eq!(validate(call), Err(InvalidTransaction::Call.into()));
+	});
+}
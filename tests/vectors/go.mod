@@ -0,0 +1,8 @@
+module github.com/perun-network/perun-polkadot-pallet/tests/vectors
+
+go 1.18
+
+require (
+	github.com/ethereum/go-ethereum v1.10.12
+	perun.network/go-perun v0.9.1
+)
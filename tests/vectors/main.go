@@ -0,0 +1,209 @@
+//  Copyright 2021 PolyCrypt GmbH
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+// Prints the keccak256 hashes of the ABI encodings that are checked in
+// `tests/encoding.rs`. Run it with `go mod tidy && go run .`.
+//
+// The params and states are encoded by the go-perun Ethereum backend.
+// The pallet extends some messages with fields that go-perun does not
+// know. They are encoded with the go-ethereum ABI package in the layout
+// that is documented in `src/encoding.rs`.
+package main
+
+import (
+	"bytes"
+	"fmt"
+	"math/big"
+
+	"github.com/ethereum/go-ethereum/accounts/abi"
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/crypto"
+	ethchannel "perun.network/go-perun/backend/ethereum/bindings/adjudicator"
+	perunabi "perun.network/go-perun/backend/ethereum/channel"
+)
+
+var (
+	alice   = common.HexToAddress("2c7536e3605d9c16a7a3d7b1898e529396a65c23")
+	bob     = common.BytesToAddress(bytes.Repeat([]byte{0x11}, 20))
+	app     = common.BytesToAddress(bytes.Repeat([]byte{0x22}, 20))
+	asset   = common.BytesToAddress(bytes.Repeat([]byte{0x33}, 20))
+	channel = [32]byte{}
+	nonce   = new(big.Int).SetBytes(bytes.Repeat([]byte{1, 2, 3, 4}, 8))
+)
+
+func main() {
+	copy(channel[:], bytes.Repeat([]byte{1}, 32))
+
+	params := ethchannel.ChannelParams{
+		ChallengeDuration: big.NewInt(60),
+		Nonce:             nonce,
+		Participants:      []common.Address{alice, bob},
+		App:               app,
+		LedgerChannel:     true,
+		VirtualChannel:    false,
+	}
+	show("params", must(perunabi.EncodeParams(&params)))
+	show("params with app params", encode(paramsExtType(false), paramsExt{
+		params.ChallengeDuration, params.Nonce, params.Participants, params.App,
+		true, false, []byte{5, 6, 7}, nil,
+	}))
+	show("params with payouts", encode(paramsExtType(true), paramsExt{
+		params.ChallengeDuration, params.Nonce, params.Participants, params.App,
+		true, false, []byte{}, []common.Address{bob, alice},
+	}))
+
+	show("final state", must(perunabi.EncodeState(newState(nil, true))))
+	show("state with data", must(perunabi.EncodeState(newState(bytes.Repeat([]byte{0xab}, 33), false))))
+
+	show("withdrawal", encode(withdrawalType(false), withdrawal{channel, alice, bob, big.NewInt(10)}))
+	show("withdrawal with expiry", encode(withdrawalType(true), withdrawalExt{
+		channel, alice, bob, big.NewInt(10), big.NewInt(1000),
+		[]common.Address{}, []*big.Int{}, big.NewInt(0), common.Address{},
+	}))
+	show("withdrawal with splits", encode(withdrawalType(true), withdrawalExt{
+		channel, alice, bob, big.NewInt(10), big.NewInt(1000),
+		[]common.Address{alice}, []*big.Int{big.NewInt(7)}, big.NewInt(0), common.Address{},
+	}))
+	show("withdrawal with fee", encode(withdrawalType(true), withdrawalExt{
+		channel, alice, bob, big.NewInt(10), big.NewInt(1000),
+		[]common.Address{}, []*big.Int{}, big.NewInt(3), bob,
+	}))
+
+	var channel2 [32]byte
+	copy(channel2[:], bytes.Repeat([]byte{2}, 32))
+	show("hire offer", encode(hireOfferType(), hireOffer{
+		channel2, alice, bob, big.NewInt(5), big.NewInt(1000),
+	}))
+}
+
+// newState returns the go-perun state of the channel with one asset.
+func newState(data []byte, final bool) *ethchannel.ChannelState {
+	return &ethchannel.ChannelState{
+		ChannelID: channel,
+		Version:   3,
+		Outcome: ethchannel.ChannelAllocation{
+			Assets:   []common.Address{asset},
+			Balances: [][]*big.Int{{big.NewInt(10), big.NewInt(20)}},
+			Locked:   []ethchannel.ChannelSubAlloc{},
+		},
+		AppData: data,
+		IsFinal: final,
+	}
+}
+
+// paramsExt are go-perun params with the app params and payouts appended.
+type paramsExt struct {
+	ChallengeDuration *big.Int
+	Nonce             *big.Int
+	Participants      []common.Address
+	App               common.Address
+	LedgerChannel     bool
+	VirtualChannel    bool
+	AppParams         []byte
+	Payouts           []common.Address
+}
+
+func paramsExtType(payouts bool) abi.Type {
+	fields := []abi.ArgumentMarshaling{
+		{Name: "challengeDuration", Type: "uint256"},
+		{Name: "nonce", Type: "uint256"},
+		{Name: "participants", Type: "address[]"},
+		{Name: "app", Type: "address"},
+		{Name: "ledgerChannel", Type: "bool"},
+		{Name: "virtualChannel", Type: "bool"},
+		{Name: "appParams", Type: "bytes"},
+	}
+	if payouts {
+		fields = append(fields, abi.ArgumentMarshaling{Name: "payouts", Type: "address[]"})
+	}
+	return tupleType(fields)
+}
+
+// withdrawal is the go-perun `WithdrawalAuth`.
+type withdrawal struct {
+	ChannelID   [32]byte
+	Participant common.Address
+	Receiver    common.Address
+	Amount      *big.Int
+}
+
+// withdrawalExt is a withdrawal with the fields of the pallet appended.
+type withdrawalExt struct {
+	ChannelID     [32]byte
+	Participant   common.Address
+	Receiver      common.Address
+	Amount        *big.Int
+	Expiry        *big.Int
+	SplitAccounts []common.Address
+	SplitAmounts  []*big.Int
+	Fee           *big.Int
+	Relayer       common.Address
+}
+
+func withdrawalType(ext bool) abi.Type {
+	fields := []abi.ArgumentMarshaling{
+		{Name: "channelID", Type: "bytes32"},
+		{Name: "participant", Type: "address"},
+		{Name: "receiver", Type: "address"},
+		{Name: "amount", Type: "uint256"},
+	}
+	if ext {
+		fields = append(fields,
+			abi.ArgumentMarshaling{Name: "expiry", Type: "uint256"},
+			abi.ArgumentMarshaling{Name: "splitAccounts", Type: "address[]"},
+			abi.ArgumentMarshaling{Name: "splitAmounts", Type: "uint256[]"},
+			abi.ArgumentMarshaling{Name: "fee", Type: "uint256"},
+			abi.ArgumentMarshaling{Name: "relayer", Type: "address"},
+		)
+	}
+	return tupleType(fields)
+}
+
+type hireOffer struct {
+	ChannelID  [32]byte
+	Registrant common.Address
+	Watcher    common.Address
+	Fee        *big.Int
+	Until      *big.Int
+}
+
+func hireOfferType() abi.Type {
+	return tupleType([]abi.ArgumentMarshaling{
+		{Name: "channelID", Type: "bytes32"},
+		{Name: "registrant", Type: "address"},
+		{Name: "watcher", Type: "address"},
+		{Name: "fee", Type: "uint256"},
+		{Name: "until", Type: "uint256"},
+	})
+}
+
+func tupleType(fields []abi.ArgumentMarshaling) abi.Type {
+	return must(abi.NewType("tuple", "", fields))
+}
+
+// encode ABI encodes `value` as the only argument of `abi.encode`.
+func encode(typ abi.Type, value interface{}) []byte {
+	return must(abi.Arguments{{Type: typ}}.Pack(value))
+}
+
+func show(name string, encoded []byte) {
+	fmt.Printf("%s: len %d, keccak256 %x\n", name, len(encoded), crypto.Keccak256(encoded))
+}
+
+func must[V any](value V, err error) V {
+	if err != nil {
+		panic(err)
+	}
+	return value
+}
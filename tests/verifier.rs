@@ -0,0 +1,57 @@
+//  Copyright 2021 PolyCrypt GmbH
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+#![cfg(feature = "batch-verify")]
+
+use pallet_perun::types::{BatchVerifier, Sr25519BatchVerifier};
+use sp_core::{crypto::Pair, ed25519, sr25519};
+use sp_runtime::{MultiSignature, MultiSigner};
+
+const MSG: &[u8] = b"perun state";
+
+fn pairs(n: usize) -> Vec<sr25519::Pair> {
+	(0..n)
+		.map(|i| sr25519::Pair::from_string(&format!("//{}", i), None).unwrap())
+		.collect()
+}
+
+#[test]
+fn batch_sr25519() {
+	let pairs = pairs(5);
+	let pks: Vec<_> = pairs.iter().map(|p| p.public()).collect();
+	let mut sigs: Vec<_> = pairs.iter().map(|p| p.sign(MSG)).collect();
+	assert!(Sr25519BatchVerifier::verify_all(MSG, &sigs, &pks));
+	assert!(!Sr25519BatchVerifier::verify_all(b"other", &sigs, &pks));
+	assert!(!Sr25519BatchVerifier::verify_all(MSG, &sigs[1..], &pks));
+
+	sigs.swap(0, 1);
+	assert!(!Sr25519BatchVerifier::verify_all(MSG, &sigs, &pks));
+}
+
+#[test]
+/// Other schemes are verified outside of the batch.
+fn batch_multi() {
+	let ed = ed25519::Pair::from_string("//Ed", None).unwrap();
+	let mut pks: Vec<MultiSigner> = pairs(3).iter().map(|p| p.public().into()).collect();
+	let mut sigs: Vec<MultiSignature> = pairs(3).iter().map(|p| p.sign(MSG).into()).collect();
+	pks.push(ed.public().into());
+	sigs.push(ed.sign(MSG).into());
+	assert!(Sr25519BatchVerifier::verify_all(MSG, &sigs, &pks));
+
+	sigs[3] = ed.sign(b"other").into();
+	assert!(!Sr25519BatchVerifier::verify_all(MSG, &sigs, &pks));
+	sigs[3] = ed.sign(MSG).into();
+	sigs[0] = pairs(1)[0].sign(b"other").into();
+	assert!(!Sr25519BatchVerifier::verify_all(MSG, &sigs, &pks));
+}
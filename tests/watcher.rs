@@ -0,0 +1,389 @@
+//  Copyright 2021 PolyCrypt GmbH
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+mod common;
+use common::mock::*;
+use common::utils::*;
+
+use frame_support::{assert_noop, assert_ok};
+use pallet_perun::types::{HireOfferOf, SigOf, StateOf, WatchReceiptOf};
+use sp_core::crypto::Pair;
+
+const FEE: u64 = 5;
+/// Duration of a hire of a channel that is never disputed.
+const HIRE_DURATION: u64 = 100;
+
+/// Returns an offer of carl to watch the channel for alice.
+fn carl_offer(setup: &Setup) -> HireOfferOf<Test> {
+	HireOfferOf::<Test> {
+		channel_id: setup.cid,
+		registrant: setup.ids.alice,
+		watcher: setup.ids.carl,
+		fee: FEE,
+		until: Perun::now() + HIRE_DURATION,
+	}
+}
+
+/// Lets alice hire carl with `offer` that is signed by `part` and `watcher`.
+fn hire(
+	setup: &Setup,
+	offer: HireOfferOf<Test>,
+	part: &sp_core::ecdsa::Pair,
+	watcher: &sp_core::ecdsa::Pair,
+) -> frame_support::dispatch::DispatchResult {
+	let payload = Perun::hire_offer_payload(&offer).unwrap();
+	Perun::hire_watcher(
+		RuntimeOrigin::signed(setup.ids.alice),
+		setup.params.clone(),
+		offer,
+		0,
+		part.sign(&payload),
+		watcher.sign(&payload),
+	)
+}
+
+/// Registers carl as watcher and lets alice hire him for the funded
+/// channel.
+fn hire_carl(setup: &Setup) {
+	deposit_both(setup);
+	assert_ok!(Perun::register_watcher(
+		RuntimeOrigin::signed(setup.ids.carl),
+		setup.keys.carl.public()
+	));
+	assert_ok!(hire(
+		setup,
+		carl_offer(setup),
+		&setup.keys.alice,
+		&setup.keys.carl
+	));
+}
+
+/// Lets carl sign a receipt for `state`.
+fn sign_receipt(setup: &Setup, state: &StateOf<Test>) -> (WatchReceiptOf<Test>, SigOf<Test>) {
+	let receipt = WatchReceiptOf::<Test> {
+		channel_id: setup.cid,
+		version: state.version,
+	};
+	let sig = setup
+		.keys
+		.carl
+		.sign(&Perun::receipt_payload(&receipt).unwrap());
+	(receipt, sig)
+}
+
+/// Concludes the disputed channel of `setup`.
+fn conclude(setup: &Setup) {
+	increment_time(2 * setup.params.challenge_duration);
+	assert_ok!(Perun::conclude(
+		RuntimeOrigin::signed(setup.ids.bob),
+		setup.params.clone()
+	));
+}
+
+#[test]
+fn watcher_register() {
+	run_test(MOCK_APP, |setup| {
+		let balance = Balances::free_balance(setup.ids.carl);
+		assert_ok!(Perun::register_watcher(
+			RuntimeOrigin::signed(setup.ids.carl),
+			setup.keys.carl.public()
+		));
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::Perun(pallet_perun::Event::WatcherRegistered(setup.ids.carl))
+		);
+		assert_eq!(
+			Balances::free_balance(setup.ids.carl),
+			balance - PerunWatcherBond::get()
+		);
+		assert_noop!(
+			Perun::register_watcher(
+				RuntimeOrigin::signed(setup.ids.carl),
+				setup.keys.carl.public()
+			),
+			pallet_perun::Error::<Test>::AlreadyWatcher
+		);
+
+		assert_ok!(Perun::unregister_watcher(RuntimeOrigin::signed(
+			setup.ids.carl
+		)));
+		assert_eq!(Balances::free_balance(setup.ids.carl), balance);
+	});
+}
+
+#[test]
+fn watcher_hire_unknown() {
+	run_test(MOCK_APP, |setup| {
+		deposit_both(setup);
+		assert_noop!(
+			hire(
+				setup,
+				carl_offer(setup),
+				&setup.keys.alice,
+				&setup.keys.carl
+			),
+			pallet_perun::Error::<Test>::UnknownWatcher
+		);
+	});
+}
+
+#[test]
+/// The watcher and a participant must sign the offer of the registrant.
+fn watcher_hire_consent() {
+	run_test(MOCK_APP, |setup| {
+		deposit_both(setup);
+		assert_ok!(Perun::register_watcher(
+			RuntimeOrigin::signed(setup.ids.carl),
+			setup.keys.carl.public()
+		));
+
+		assert_noop!(
+			hire(
+				setup,
+				carl_offer(setup),
+				&setup.keys.alice,
+				&setup.keys.alice
+			),
+			pallet_perun::Error::<Test>::InvalidSignature
+		);
+		assert_noop!(
+			hire(setup, carl_offer(setup), &setup.keys.carl, &setup.keys.carl),
+			pallet_perun::Error::<Test>::InvalidSignature
+		);
+		let offer = HireOfferOf::<Test> {
+			registrant: setup.ids.bob,
+			..carl_offer(setup)
+		};
+		assert_noop!(
+			hire(setup, offer, &setup.keys.alice, &setup.keys.carl),
+			pallet_perun::Error::<Test>::InvalidRegistrant
+		);
+		let offer = HireOfferOf::<Test> {
+			until: Perun::now(),
+			..carl_offer(setup)
+		};
+		assert_noop!(
+			hire(setup, offer, &setup.keys.alice, &setup.keys.carl),
+			pallet_perun::Error::<Test>::HireExpired
+		);
+	});
+}
+
+#[test]
+/// Only funded channels can be watched.
+fn watcher_hire_unfunded() {
+	run_test(MOCK_APP, |setup| {
+		assert_ok!(Perun::register_watcher(
+			RuntimeOrigin::signed(setup.ids.carl),
+			setup.keys.carl.public()
+		));
+
+		assert_noop!(
+			hire(
+				setup,
+				carl_offer(setup),
+				&setup.keys.alice,
+				&setup.keys.carl
+			),
+			pallet_perun::Error::<Test>::UnknownChannel
+		);
+	});
+}
+
+#[test]
+/// The hire of a channel that is never disputed can be released after
+/// its end and the fee is returned.
+fn watcher_hire_released() {
+	run_test(MOCK_APP, |setup| {
+		let balance = Balances::free_balance(setup.ids.alice);
+		hire_carl(setup);
+		assert_noop!(
+			Perun::release_hire(RuntimeOrigin::signed(setup.ids.bob), setup.cid),
+			pallet_perun::Error::<Test>::TooEarly
+		);
+
+		increment_time(HIRE_DURATION + 1);
+		assert_ok!(Perun::release_hire(
+			RuntimeOrigin::signed(setup.ids.bob),
+			setup.cid
+		));
+		assert_eq!(
+			Balances::free_balance(setup.ids.alice),
+			balance - setup.state.balances[0]
+		);
+		assert_eq!(Perun::hires(setup.cid), None);
+		assert_ok!(Perun::unregister_watcher(RuntimeOrigin::signed(
+			setup.ids.carl
+		)));
+	});
+}
+
+#[test]
+/// The hire of a disputed channel does not end before the conclusion.
+fn watcher_hire_disputed() {
+	run_test(MOCK_APP, |setup| {
+		hire_carl(setup);
+		assert_ok!(dispute_as(setup, setup.ids.bob, &setup.state));
+		increment_time(HIRE_DURATION + 1);
+
+		assert_noop!(
+			Perun::release_hire(RuntimeOrigin::signed(setup.ids.bob), setup.cid),
+			pallet_perun::Error::<Test>::DisputeActive
+		);
+	});
+}
+
+#[test]
+/// A watcher that refutes a stale dispute is paid its fee.
+fn watcher_paid() {
+	run_test(MOCK_APP, |setup| {
+		hire_carl(setup);
+		assert_noop!(
+			Perun::unregister_watcher(RuntimeOrigin::signed(setup.ids.carl)),
+			pallet_perun::Error::<Test>::WatcherHired
+		);
+		assert_ok!(dispute_as(setup, setup.ids.bob, &setup.state));
+		let balance = Balances::free_balance(setup.ids.carl);
+
+		assert_ok!(dispute_as(setup, setup.ids.carl, &next_state(setup)));
+		assert_eq!(Balances::free_balance(setup.ids.carl), balance + FEE);
+		assert!(System::events().iter().any(|r| r.event
+			== RuntimeEvent::Perun(pallet_perun::Event::WatcherPaid(
+				setup.cid,
+				setup.ids.carl,
+				FEE
+			))));
+		// The watcher stays hired until the channel is concluded.
+		assert_eq!(Perun::hires(setup.cid).unwrap().fee, 0);
+		assert_eq!(Perun::watchers(setup.ids.carl).unwrap().hires, 1);
+	});
+}
+
+#[test]
+/// A paid watcher is slashed if the channel concludes with a state that
+/// is older than its receipt.
+fn watcher_paid_slashed() {
+	run_test(MOCK_APP, |setup| {
+		hire_carl(setup);
+		let mut newest = next_state(setup);
+		newest.version += 1;
+		let (receipt, sig) = sign_receipt(setup, &newest);
+		assert_ok!(dispute_as(setup, setup.ids.bob, &setup.state));
+		assert_ok!(dispute_as(setup, setup.ids.carl, &next_state(setup)));
+		conclude(setup);
+
+		assert_ok!(Perun::slash_watcher(
+			RuntimeOrigin::signed(setup.ids.alice),
+			receipt,
+			sig
+		));
+		assert_eq!(Perun::watchers(setup.ids.carl), None);
+	});
+}
+
+#[test]
+/// The fee is returned if the watcher did not refute and the watcher is
+/// only released after the slash period.
+fn watcher_fee_returned() {
+	run_test(MOCK_APP, |setup| {
+		hire_carl(setup);
+		let balance = Balances::free_balance(setup.ids.alice);
+		assert_ok!(dispute_as(setup, setup.ids.bob, &setup.state));
+		conclude(setup);
+		assert_eq!(Balances::free_balance(setup.ids.alice), balance + FEE);
+
+		assert_noop!(
+			Perun::release_hire(RuntimeOrigin::signed(setup.ids.carl), setup.cid),
+			pallet_perun::Error::<Test>::TooEarly
+		);
+		increment_time(PerunWatcherSlashPeriod::get() + 1);
+		assert_ok!(Perun::release_hire(
+			RuntimeOrigin::signed(setup.ids.carl),
+			setup.cid
+		));
+		assert_ok!(Perun::unregister_watcher(RuntimeOrigin::signed(
+			setup.ids.carl
+		)));
+	});
+}
+
+#[test]
+/// A watcher that missed a state for which it signed a receipt is slashed.
+fn watcher_slashed() {
+	run_test(MOCK_APP, |setup| {
+		hire_carl(setup);
+		let (receipt, sig) = sign_receipt(setup, &next_state(setup));
+		assert_ok!(dispute_as(setup, setup.ids.bob, &setup.state));
+		conclude(setup);
+		let balance = Balances::free_balance(setup.ids.alice);
+
+		assert_ok!(Perun::slash_watcher(
+			RuntimeOrigin::signed(setup.ids.alice),
+			receipt,
+			sig
+		));
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::Perun(pallet_perun::Event::WatcherSlashed(
+				setup.cid,
+				setup.ids.carl,
+				PerunWatcherBond::get()
+			))
+		);
+		assert_eq!(
+			Balances::free_balance(setup.ids.alice),
+			balance + PerunWatcherBond::get()
+		);
+		assert_eq!(Perun::watchers(setup.ids.carl), None);
+	});
+}
+
+#[test]
+fn watcher_not_negligent() {
+	run_test(MOCK_APP, |setup| {
+		hire_carl(setup);
+		let (receipt, sig) = sign_receipt(setup, &setup.state);
+		assert_ok!(dispute_as(setup, setup.ids.bob, &setup.state));
+
+		assert_noop!(
+			Perun::slash_watcher(
+				RuntimeOrigin::signed(setup.ids.alice),
+				receipt.clone(),
+				sig.clone()
+			),
+			pallet_perun::Error::<Test>::NotConcluded
+		);
+		conclude(setup);
+		assert_noop!(
+			Perun::slash_watcher(RuntimeOrigin::signed(setup.ids.alice), receipt, sig),
+			pallet_perun::Error::<Test>::WatcherNotNegligent
+		);
+	});
+}
+
+#[test]
+fn watcher_slash_period_over() {
+	run_test(MOCK_APP, |setup| {
+		hire_carl(setup);
+		let (receipt, sig) = sign_receipt(setup, &next_state(setup));
+		assert_ok!(dispute_as(setup, setup.ids.bob, &setup.state));
+		conclude(setup);
+		increment_time(PerunWatcherSlashPeriod::get() + 1);
+
+		assert_noop!(
+			Perun::slash_watcher(RuntimeOrigin::signed(setup.ids.alice), receipt, sig),
+			pallet_perun::Error::<Test>::SlashPeriodOver
+		);
+	});
+}
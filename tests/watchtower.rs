@@ -0,0 +1,188 @@
+//  Copyright 2021 PolyCrypt GmbH
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+mod common;
+use common::mock::*;
+use common::utils::*;
+
+use frame_support::{assert_ok, traits::Hooks};
+use pallet_perun::{types::StateOf, watchtower::WatchedStateOf};
+use sp_runtime::offchain::storage::StorageValueRef;
+
+/// Lets the watchtower watch `state` and runs the off-chain worker.
+fn watch_and_run(setup: &Setup, state: &StateOf<Test>) -> Vec<pallet_perun::Call<Test>> {
+	Perun::watch(
+		setup.params.clone(),
+		state.clone(),
+		sign_state(state, setup),
+	);
+	Perun::offchain_worker(System::block_number());
+	take_submitted()
+}
+
+#[test]
+fn watchtower_dispute() {
+	run_test(MOCK_APP, |setup| {
+		call_dispute(setup, false);
+		let state = next_state(setup);
+
+		assert_eq!(
+			watch_and_run(setup, &state),
+			vec![pallet_perun::Call::dispute {
+				params: setup.params.clone(),
+				state: state.clone(),
+				state_sigs: sign_state(&state, setup),
+			}]
+		);
+	});
+}
+
+#[test]
+fn watchtower_up_to_date() {
+	run_test(MOCK_APP, |setup| {
+		// Channels without dispute are ignored.
+		assert_eq!(watch_and_run(setup, &next_state(setup)), vec![]);
+
+		call_dispute(setup, false);
+		assert_eq!(watch_and_run(setup, &setup.state), vec![]);
+	});
+}
+
+#[test]
+/// A channel that is not watched is not refuted.
+fn watchtower_unwatched() {
+	run_test(MOCK_APP, |setup| {
+		call_dispute(setup, false);
+		Perun::offchain_worker(System::block_number());
+		assert_eq!(take_submitted(), vec![]);
+	});
+}
+
+#[test]
+/// An app channel is progressed after the timeout.
+fn watchtower_progress() {
+	run_test(MOCK_APP, |setup| {
+		call_dispute(setup, false);
+		increment_time(setup.params.challenge_duration + 1);
+		let mut state = next_state(setup);
+		state.data = MOCK_DATA_VALID.to_vec();
+
+		assert_eq!(
+			watch_and_run(setup, &state),
+			vec![pallet_perun::Call::progress {
+				params: setup.params.clone(),
+				next: state.clone(),
+				sig: sign_state(&state, setup)[0].clone(),
+				signer: 0,
+			}]
+		);
+	});
+}
+
+#[test]
+/// A channel without app cannot be refuted after the timeout.
+fn watchtower_timed_out() {
+	run_test(NO_APP, |setup| {
+		call_dispute(setup, false);
+		increment_time(setup.params.challenge_duration + 1);
+
+		assert_eq!(watch_and_run(setup, &next_state(setup)), vec![]);
+	});
+}
+
+#[test]
+fn watchtower_conclude_final() {
+	run_test(MOCK_APP, |setup| {
+		call_dispute(setup, false);
+		let mut state = next_state(setup);
+		state.finalized = true;
+
+		assert_eq!(
+			watch_and_run(setup, &state),
+			vec![pallet_perun::Call::conclude_final {
+				params: setup.params.clone(),
+				state: state.clone(),
+				state_sigs: sign_state(&state, setup),
+			}]
+		);
+	});
+}
+
+#[test]
+/// A refutation is only submitted again after the unsigned longevity.
+fn watchtower_submitted_once() {
+	run_test(MOCK_APP, |setup| {
+		call_dispute(setup, false);
+		let state = next_state(setup);
+		assert_eq!(watch_and_run(setup, &state).len(), 1);
+
+		// Same block and next block: already submitted.
+		Perun::offchain_worker(System::block_number());
+		assert_eq!(take_submitted(), vec![]);
+		increment_time(1);
+		Perun::offchain_worker(System::block_number());
+		assert_eq!(take_submitted(), vec![]);
+
+		// A newer state is submitted right away.
+		let mut newer = state.clone();
+		newer.version += 1;
+		assert_eq!(watch_and_run(setup, &newer).len(), 1);
+
+		// The dropped refutation is submitted again.
+		increment_time(PerunUnsignedLongevity::get());
+		Perun::offchain_worker(System::block_number());
+		assert_eq!(take_submitted().len(), 1);
+	});
+}
+
+#[test]
+/// Rejected refutations are submitted again in the next block.
+fn watchtower_rejected() {
+	run_test(MOCK_APP, |setup| {
+		call_dispute(setup, false);
+		let state = next_state(setup);
+		WatchtowerAccepts::set(false);
+		assert_eq!(watch_and_run(setup, &state).len(), 1);
+
+		WatchtowerAccepts::set(true);
+		increment_time(1);
+		Perun::offchain_worker(System::block_number());
+		assert_eq!(take_submitted().len(), 1);
+		// Accepted refutations are not submitted again.
+		increment_time(1);
+		Perun::offchain_worker(System::block_number());
+		assert_eq!(take_submitted(), vec![]);
+	});
+}
+
+#[test]
+/// Concluded channels are no longer watched.
+fn watchtower_concluded() {
+	run_test(MOCK_APP, |setup| {
+		deposit_both(setup);
+		call_dispute(setup, false);
+		increment_time(2 * setup.params.challenge_duration);
+		assert_ok!(Perun::conclude(
+			RuntimeOrigin::signed(setup.ids.carl),
+			setup.params.clone()
+		));
+
+		assert_eq!(watch_and_run(setup, &next_state(setup)), vec![]);
+		let key = Perun::watch_key(setup.cid);
+		assert!(matches!(
+			StorageValueRef::persistent(&key).get::<WatchedStateOf<Test>>(),
+			Ok(None)
+		));
+	});
+}
@@ -16,7 +16,6 @@ mod common;
 use common::mock::*;
 use common::utils::*;
 
-use codec::Encode;
 use frame_support::{assert_noop, assert_ok};
 use pallet_perun::types::WithdrawalOf;
 use sp_core::crypto::Pair;
@@ -28,6 +27,10 @@ fn withdraw_invalid_sig() {
 			channel_id: setup.cid,
 			receiver: setup.ids.alice,
 			part: setup.keys.alice.public(),
+			expiry: None,
+			splits: vec![],
+			fee: 0,
+			relayer: None,
 		};
 
 		assert_noop!(
@@ -48,6 +51,10 @@ fn withdraw_unknown_channel() {
 			channel_id: setup.cid,
 			receiver: setup.ids.alice,
 			part: setup.keys.alice.public(),
+			expiry: None,
+			splits: vec![],
+			fee: 0,
+			relayer: None,
 		};
 		let sigs = sign_withdrawal(&withdrawal, setup);
 
@@ -72,6 +79,10 @@ fn withdraw_not_concluded() {
 			channel_id: setup.cid,
 			receiver: setup.ids.alice,
 			part: setup.keys.alice.public(),
+			expiry: None,
+			splits: vec![],
+			fee: 0,
+			relayer: None,
 		};
 		let sigs = sign_withdrawal(&withdrawal, setup);
 
@@ -104,8 +115,12 @@ fn withdraw_unknown_participant() {
 			channel_id: setup.cid,
 			receiver: setup.ids.alice,
 			part: setup.keys.carl.public(), //  Carl is not part of the channel
+			expiry: None,
+			splits: vec![],
+			fee: 0,
+			relayer: None,
 		};
-		let raw = Encode::encode(&withdrawal);
+		let raw = Perun::withdrawal_payload(&withdrawal, &0).unwrap();
 		let sig_carl = setup.keys.carl.sign(&raw);
 
 		assert_noop!(
@@ -148,6 +163,10 @@ fn withdraw_ok() {
 				channel_id: setup.cid,
 				receiver: setup.ids.alice,
 				part: setup.keys.alice.public(),
+				expiry: None,
+				splits: vec![],
+				fee: 0,
+				relayer: None,
 			};
 			let sigs = sign_withdrawal(&withdrawal, setup);
 
@@ -175,6 +194,10 @@ fn withdraw_ok() {
 				channel_id: setup.cid,
 				receiver: setup.ids.bob,
 				part: setup.keys.bob.public(),
+				expiry: None,
+				splits: vec![],
+				fee: 0,
+				relayer: None,
 			};
 			let sigs = sign_withdrawal(&withdrawal, setup);
 
@@ -198,3 +221,88 @@ fn withdraw_ok() {
 		}
 	});
 }
+
+#[test]
+fn withdraw_expired() {
+	run_test(MOCK_APP, |setup| {
+		deposit_both(&setup);
+		let mut state = setup.state.clone();
+		state.finalized = true;
+		let sigs = sign_state(&state, &setup);
+		assert_ok!(Perun::conclude_final(
+			RuntimeOrigin::signed(setup.ids.alice),
+			setup.params.clone(),
+			state.clone(),
+			sigs
+		));
+
+		let withdrawal = WithdrawalOf::<Test> {
+			channel_id: setup.cid,
+			receiver: setup.ids.alice,
+			part: setup.keys.alice.public(),
+			expiry: Some(Perun::now() + 5),
+			splits: vec![],
+			fee: 0,
+			relayer: None,
+		};
+		let sigs = sign_withdrawal(&withdrawal, setup);
+
+		increment_time(6);
+		assert_noop!(
+			Perun::withdraw(
+				RuntimeOrigin::signed(setup.ids.alice),
+				withdrawal,
+				sigs[0].clone()
+			),
+			pallet_perun::Error::<Test>::WithdrawalExpired
+		);
+	});
+}
+
+#[test]
+/// A withdrawal cannot be replayed since the funding id of a concluded
+/// channel does not accept new deposits.
+fn withdraw_replay() {
+	run_test(MOCK_APP, |setup| {
+		deposit_both(&setup);
+		let mut state = setup.state.clone();
+		state.finalized = true;
+		let sigs = sign_state(&state, &setup);
+		assert_ok!(Perun::conclude_final(
+			RuntimeOrigin::signed(setup.ids.alice),
+			setup.params.clone(),
+			state.clone(),
+			sigs
+		));
+
+		let withdrawal = WithdrawalOf::<Test> {
+			channel_id: setup.cid,
+			receiver: setup.ids.alice,
+			part: setup.keys.alice.public(),
+			expiry: None,
+			splits: vec![],
+			fee: 0,
+			relayer: None,
+		};
+		let sigs = sign_withdrawal(&withdrawal, setup);
+		assert_ok!(Perun::withdraw(
+			RuntimeOrigin::signed(setup.ids.alice),
+			withdrawal.clone(),
+			sigs[0].clone()
+		));
+
+		// Nobody can deposit into the funding id again.
+		assert_noop!(
+			Perun::deposit(RuntimeOrigin::signed(setup.ids.carl), setup.fids.alice, 10),
+			pallet_perun::Error::<Test>::AlreadyConcluded
+		);
+		assert_noop!(
+			Perun::withdraw(
+				RuntimeOrigin::signed(setup.ids.carl),
+				withdrawal,
+				sigs[0].clone()
+			),
+			pallet_perun::Error::<Test>::UnknownDeposit
+		);
+	});
+}
@@ -0,0 +1,138 @@
+//  Copyright 2021 PolyCrypt GmbH
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+mod common;
+use common::mock::*;
+use common::utils::*;
+
+use frame_support::{assert_noop, assert_ok};
+use pallet_perun::types::{SigOf, WithdrawalOf};
+use sp_core::{crypto::Pair, H256};
+
+/// Concludes the funded channel of `setup` with swapped balances.
+fn conclude_swapped(setup: &Setup) {
+	deposit_both(setup);
+	let mut state = setup.state.clone();
+	state.finalized = true;
+	state.balances = vec![state.balances[1], state.balances[0]];
+	assert_ok!(Perun::conclude_final(
+		RuntimeOrigin::signed(setup.ids.carl),
+		setup.params.clone(),
+		state.clone(),
+		sign_state(&state, setup)
+	));
+}
+
+/// Returns the signed withdrawals of alice and bob to their accounts.
+fn withdrawals(setup: &Setup) -> (Vec<WithdrawalOf<Test>>, Vec<SigOf<Test>>) {
+	let parts = [
+		(setup.keys.alice.public(), setup.ids.alice),
+		(setup.keys.bob.public(), setup.ids.bob),
+	];
+	let withdrawals: Vec<_> = parts
+		.into_iter()
+		.map(|(part, receiver)| WithdrawalOf::<Test> {
+			channel_id: setup.cid,
+			part,
+			receiver,
+			expiry: None,
+			splits: vec![],
+			fee: 0,
+			relayer: None,
+		})
+		.collect();
+	let sigs = withdrawals
+		.iter()
+		.enumerate()
+		.map(|(i, w)| sign_withdrawal(w, setup)[i].clone())
+		.collect();
+	(withdrawals, sigs)
+}
+
+#[test]
+fn withdraw_all_ok() {
+	run_test(MOCK_APP, |setup| {
+		conclude_swapped(setup);
+		let (withdrawals, sigs) = withdrawals(setup);
+
+		assert_ok!(Perun::withdraw_all(
+			RuntimeOrigin::signed(setup.ids.carl),
+			setup.cid,
+			withdrawals,
+			sigs
+		));
+		assert_eq!(Balances::free_balance(setup.ids.alice), 95);
+		assert_eq!(Balances::free_balance(setup.ids.bob), 105);
+		assert_eq!(Perun::deposits(setup.fids.alice), None);
+		assert_eq!(Perun::deposits(setup.fids.bob), None);
+		event_withdrawn(setup.fids.bob);
+	});
+}
+
+#[test]
+/// Nothing is withdrawn if one withdrawal is invalid.
+fn withdraw_all_invalid_sig() {
+	run_test(MOCK_APP, |setup| {
+		conclude_swapped(setup);
+		let (withdrawals, mut sigs) = withdrawals(setup);
+		sigs[1] = sigs[0].clone();
+
+		assert_noop!(
+			Perun::withdraw_all(
+				RuntimeOrigin::signed(setup.ids.carl),
+				setup.cid,
+				withdrawals,
+				sigs
+			),
+			pallet_perun::Error::<Test>::InvalidSignature
+		);
+	});
+}
+
+#[test]
+fn withdraw_all_invalid_channel() {
+	run_test(MOCK_APP, |setup| {
+		conclude_swapped(setup);
+		let (withdrawals, sigs) = withdrawals(setup);
+
+		assert_noop!(
+			Perun::withdraw_all(
+				RuntimeOrigin::signed(setup.ids.carl),
+				H256::repeat_byte(1),
+				withdrawals,
+				sigs
+			),
+			pallet_perun::Error::<Test>::InvalidChannelId
+		);
+	});
+}
+
+#[test]
+fn withdraw_all_invalid_sig_num() {
+	run_test(MOCK_APP, |setup| {
+		conclude_swapped(setup);
+		let (withdrawals, mut sigs) = withdrawals(setup);
+		sigs.pop();
+
+		assert_noop!(
+			Perun::withdraw_all(
+				RuntimeOrigin::signed(setup.ids.carl),
+				setup.cid,
+				withdrawals,
+				sigs
+			),
+			pallet_perun::Error::<Test>::InvalidSignatureNum
+		);
+	});
+}